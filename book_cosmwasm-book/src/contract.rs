@@ -1,395 +1,4898 @@
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, GreetResp, InstantiateMsg, QueryMsg},
-    state::{ADMINS, DONATION_DENOM},
+    msg::{
+        ExecuteMsg, GreetResp, InstantiateMsg, MigrateMsg, PayoutMode, QueryMsg, RemovalPolicy,
+        Role, SudoMsg,
+    },
+    state::{
+        ADMINS, ADMIN_COUNT, ADMIN_EXPIRY, CW20_TOKENS, DONATION_COUNT, DONATION_DENOMS, GUARDIAN,
+        HOOKS, LEGACY_ADMINS, LEGACY_ADMIN_WEIGHTS, LEGACY_DONATION_DENOM, LEGACY_TOTAL_WEIGHT,
+        MIN_ADMINS, PAYOUT_MODE, PROPOSAL_COUNT, REMOVAL_POLICY, ROLES, TOTAL_WEIGHT, VOTING,
+    },
 };
-use cosmwasm_std::{to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+use cosmwasm_std::{
+    to_binary, Addr, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Order, Response, StdResult,
+    Storage,
+};
+use cw2::ContractVersion;
+use cw_storage_plus::{Item, Map};
+
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+const DEFAULT_LIMIT: u32 = 10;
+const MAX_LIMIT: u32 = 30;
+
 // init
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    env: Env,
+    info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
-    let admins: StdResult<Vec<_>> = msg
-        .admins
-        .into_iter()
-        .map(|addr| deps.api.addr_validate(&addr))
-        .collect();
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    let owner = match msg.owner {
+        Some(owner) => deps.api.addr_validate(&owner)?,
+        None => info.sender,
+    };
+    ROLES.save(deps.storage, (&owner, Role::Owner.as_str()), &Empty {})?;
+    ADMIN_COUNT.save(deps.storage, &0)?;
+    TOTAL_WEIGHT.save(deps.storage, &0, env.block.height)?;
+    let admins = exec::validate_new_members(deps.as_ref(), &env, &msg.admins)?;
+    for (admin, member) in admins.iter().zip(&msg.admins) {
+        exec::add_admin(deps.storage, admin, member.weight, env.block.height)?;
+        exec::set_expiry(deps.storage, admin, member.expires)?;
+    }
+    for denom in &msg.donation_denoms {
+        DONATION_DENOMS.save(deps.storage, denom, &Empty {})?;
+    }
+    for token in &msg.cw20_tokens {
+        let token = deps.api.addr_validate(token)?;
+        CW20_TOKENS.save(deps.storage, &token, &Empty {})?;
+    }
 
-    ADMINS.save(deps.storage, &admins?)?;
-    DONATION_DENOM.save(deps.storage, &msg.donation_denom)?;
+    if let Some(voting) = msg.voting {
+        exec::validate_voting(&voting)?;
+        VOTING.save(deps.storage, &voting)?;
+    }
+    PROPOSAL_COUNT.save(deps.storage, &0)?;
+    DONATION_COUNT.save(deps.storage, &0)?;
+    if let PayoutMode::Vesting { cliff, duration } = msg.payout_mode {
+        if duration == 0 || cliff > duration {
+            return Err(ContractError::InvalidVesting {});
+        }
+    }
+    PAYOUT_MODE.save(deps.storage, &msg.payout_mode)?;
+    REMOVAL_POLICY.save(deps.storage, &msg.removal_policy)?;
+    MIN_ADMINS.save(deps.storage, &msg.min_admins.unwrap_or(1))?;
+    if let Some(guardian) = msg.guardian {
+        let guardian = deps.api.addr_validate(&guardian)?;
+        GUARDIAN.save(deps.storage, &guardian)?;
+    }
 
     Ok(Response::new())
 }
 // execute
 #[allow(dead_code)]
 pub fn execute(
-    deps: DepsMut,
-    _env: Env,
+    mut deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     use ExecuteMsg::*;
 
-    match msg {
-        AddMembers { admins } => exec::add_members(deps, info, admins),
-        Leave {} => exec::leave(deps, info).map_err(Into::into),
-        Donate {} => exec::donate(deps, info),
-    }
+    // Lapsed memberships are dropped before anything gets authorized or split.
+    let (expired, diffs) = exec::prune_expired(deps.branch(), &env)?;
+    let hooks = exec::hook_messages(deps.storage, diffs)?;
+
+    let resp = match msg {
+        AddMembers { admins } => exec::add_members(deps, env, info, admins),
+        Leave {} => exec::leave(deps, env, info),
+        RemoveMembers { admins } => exec::remove_members(deps, env, info, admins),
+        Reseed { admins } => exec::reseed(deps, env, info, admins),
+        AddHook { addr } => exec::add_hook(deps, info, addr),
+        RemoveHook { addr } => exec::remove_hook(deps, info, addr),
+        GrantRole { addr, role } => exec::grant_role(deps, info, addr, role),
+        RevokeRole { addr, role } => exec::revoke_role(deps, info, addr, role),
+        RenewMembership { addr, expires } => exec::renew_membership(deps, env, info, addr, expires),
+        Donate {} => exec::donate(deps, env, info),
+        Receive(msg) => exec::receive(deps, env, info, msg),
+        UpdateWeights { admins } => exec::update_weights(deps, env, info, admins),
+        UpdateDenoms { add, remove } => exec::update_denoms(deps, info, add, remove),
+        UpdateCw20Tokens { add, remove } => exec::update_cw20_tokens(deps, info, add, remove),
+        Claim {} => exec::claim(deps, info),
+        WithdrawVested { start_after, limit } => {
+            exec::withdraw_vested(deps, env, info, start_after, limit)
+        }
+        Propose { action } => exec::propose(deps, env, info, action),
+        Vote { proposal_id, vote } => exec::vote(deps, env, info, proposal_id, vote),
+    }?;
+
+    Ok(resp.add_events(expired).add_submessages(hooks))
 }
 
 mod exec {
-    use cosmwasm_std::{coins, BankMsg, Event};
+    use cosmwasm_std::{
+        from_binary, Addr, BankMsg, BlockInfo, Coin, CosmosMsg, Decimal, Decimal256, Event,
+        StdError, Storage, SubMsg, Uint128, WasmMsg,
+    };
+    use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+    use cw4::{MemberChangedHookMsg, MemberDiff};
+    use cw_storage_plus::{Bound, PrefixBound};
+    use cw_utils::{Expiration, PaymentError};
 
     use super::*;
     use crate::error::ContractError;
+    use crate::events::{
+        AdminAdded, AdminExpired, AdminRemoved, RemovalApproved, RoleGranted, RoleRevoked,
+    };
+    use crate::msg::{
+        DonationSplit, Member, ProposalAction, ProposalStatus, ReceiveMsg, RemovalPolicy,
+        Threshold, VoteOption, VotingConfig,
+    };
+    use crate::state::{
+        donations, DonationRecord, Proposal, VestingStream, ADMIN_EXPIRY, ADMIN_REWARDS,
+        CW20_PREFIX, DUST, EXPIRING_AT_HEIGHT, EXPIRING_AT_TIME, PROPOSALS, REMOVAL_APPROVALS,
+        REWARD_INDEX, VESTING, VOTES,
+    };
 
-    pub fn add_members(
+    pub fn validate_voting(voting: &VotingConfig) -> Result<(), ContractError> {
+        match voting.threshold {
+            Threshold::AbsoluteCount { count: 0 } => {
+                return Err(ContractError::InvalidThreshold {})
+            }
+            Threshold::Percentage { percentage }
+                if percentage.is_zero() || percentage > Decimal::one() =>
+            {
+                return Err(ContractError::InvalidThreshold {})
+            }
+            _ => (),
+        }
+
+        if voting.max_voting_period == cw_utils::Duration::Height(0)
+            || voting.max_voting_period == cw_utils::Duration::Time(0)
+        {
+            return Err(ContractError::InvalidVotingPeriod {});
+        }
+
+        Ok(())
+    }
+
+    /// Inserts `admin` with `weight`, or updates the weight of an existing admin.
+    /// The returned diff has no `old` weight if the admin is new.
+    pub fn add_admin(
+        storage: &mut dyn Storage,
+        admin: &Addr,
+        weight: u64,
+        height: u64,
+    ) -> StdResult<MemberDiff> {
+        let old_weight = ADMINS.may_load(storage, admin)?;
+        settle_rewards(storage, admin, old_weight.unwrap_or_default())?;
+
+        ADMINS.save(storage, admin, &weight, height)?;
+        TOTAL_WEIGHT.update(storage, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() - old_weight.unwrap_or_default() + weight)
+        })?;
+        if old_weight.is_none() {
+            ADMIN_COUNT.update(storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+        }
+
+        Ok(MemberDiff::new(admin, old_weight, Some(weight)))
+    }
+
+    /// Builds the `MemberChangedHookMsg` calls announcing `diffs` to every hook.
+    pub fn hook_messages(storage: &dyn Storage, diffs: Vec<MemberDiff>) -> StdResult<Vec<SubMsg>> {
+        if diffs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        HOOKS.prepare_hooks(storage, |hook| {
+            MemberChangedHookMsg::new(diffs.clone())
+                .into_cosmos_msg(hook)
+                .map(SubMsg::new)
+        })
+    }
+
+    pub fn add_hook(
         deps: DepsMut,
         info: MessageInfo,
-        admins: Vec<String>,
+        addr: String,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::MembershipManager)?;
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.add_hook(deps.storage, hook.clone())?;
+
+        let resp = Response::new()
+            .add_attribute("action", "add_hook")
+            .add_attribute("hook", hook);
+
+        Ok(resp)
+    }
+
+    pub fn remove_hook(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
     ) -> Result<Response, ContractError> {
-        let mut curr_admins = ADMINS.load(deps.storage)?;
-        if !curr_admins.contains(&info.sender) {
+        ensure_role(deps.as_ref(), &info.sender, Role::MembershipManager)?;
+        let hook = deps.api.addr_validate(&addr)?;
+        HOOKS.remove_hook(deps.storage, hook.clone())?;
+
+        let resp = Response::new()
+            .add_attribute("action", "remove_hook")
+            .add_attribute("hook", hook);
+
+        Ok(resp)
+    }
+
+    /// Rejects memberships that would be over before they start.
+    pub fn validate_expiry(env: &Env, expires: Option<Expiration>) -> Result<(), ContractError> {
+        match expires {
+            Some(expires) if expires.is_expired(&env.block) => {
+                Err(ContractError::InvalidExpiration { expires })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Validates members about to join, rejecting duplicates within the list
+    /// and addresses that already are admins.
+    pub fn validate_new_members(
+        deps: Deps,
+        env: &Env,
+        members: &[Member],
+    ) -> Result<Vec<Addr>, ContractError> {
+        let mut admins: Vec<Addr> = Vec::with_capacity(members.len());
+        for member in members {
+            let admin = deps.api.addr_validate(&member.addr)?;
+            if admins.contains(&admin) {
+                return Err(ContractError::DuplicateAdmin { addr: admin });
+            }
+            if ADMINS.may_load(deps.storage, &admin)?.is_some() {
+                return Err(ContractError::AlreadyAdmin { addr: admin });
+            }
+            validate_expiry(env, member.expires)?;
+            admins.push(admin);
+        }
+
+        Ok(admins)
+    }
+
+    pub fn set_expiry(
+        storage: &mut dyn Storage,
+        admin: &Addr,
+        expires: Option<Expiration>,
+    ) -> StdResult<()> {
+        clear_expiry(storage, admin)?;
+        match expires {
+            Some(Expiration::Never {}) | None => {}
+            Some(expires) => {
+                ADMIN_EXPIRY.save(storage, admin, &expires)?;
+                queue_expiry(storage, admin, &expires)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Indexes `admin` under the height or time its membership lapses at.
+    pub fn queue_expiry(
+        storage: &mut dyn Storage,
+        admin: &Addr,
+        expires: &Expiration,
+    ) -> StdResult<()> {
+        match expires {
+            Expiration::AtHeight(height) => {
+                EXPIRING_AT_HEIGHT.save(storage, (*height, admin), &Empty {})
+            }
+            Expiration::AtTime(time) => {
+                EXPIRING_AT_TIME.save(storage, (time.nanos(), admin), &Empty {})
+            }
+            Expiration::Never {} => Ok(()),
+        }
+    }
+
+    fn clear_expiry(storage: &mut dyn Storage, admin: &Addr) -> StdResult<()> {
+        match ADMIN_EXPIRY.may_load(storage, admin)? {
+            Some(Expiration::AtHeight(height)) => {
+                EXPIRING_AT_HEIGHT.remove(storage, (height, admin))
+            }
+            Some(Expiration::AtTime(time)) => {
+                EXPIRING_AT_TIME.remove(storage, (time.nanos(), admin))
+            }
+            Some(Expiration::Never {}) | None => {}
+        }
+        ADMIN_EXPIRY.remove(storage, admin);
+
+        Ok(())
+    }
+
+    /// Admins whose membership lapsed by `block` and who were not pruned yet.
+    /// Only the lapsed entries of the expiry queues are visited.
+    pub fn lapsed_admins(storage: &dyn Storage, block: &BlockInfo) -> StdResult<Vec<Addr>> {
+        let by_height = EXPIRING_AT_HEIGHT.prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(block.height)),
+            Order::Ascending,
+        );
+        let by_time = EXPIRING_AT_TIME.prefix_range(
+            storage,
+            None,
+            Some(PrefixBound::inclusive(block.time.nanos())),
+            Order::Ascending,
+        );
+
+        by_height
+            .chain(by_time)
+            .map(|item| item.map(|((_, admin), _)| admin))
+            .collect()
+    }
+
+    /// Removes every admin whose membership has lapsed, returning an
+    /// `admin_expired` event and a diff for each.
+    pub fn prune_expired(deps: DepsMut, env: &Env) -> StdResult<(Vec<Event>, Vec<MemberDiff>)> {
+        let expired = lapsed_admins(deps.storage, &env.block)?;
+
+        let mut events = vec![];
+        let mut diffs = vec![];
+        for admin in expired {
+            diffs.extend(remove_admin(deps.storage, &admin, env.block.height)?);
+            events.push(AdminExpired { addr: admin }.into());
+        }
+
+        Ok((events, diffs))
+    }
+
+    /// Removes `admin` from the set, returning `None` if it was not there.
+    pub fn remove_admin(
+        storage: &mut dyn Storage,
+        admin: &Addr,
+        height: u64,
+    ) -> StdResult<Option<MemberDiff>> {
+        let weight = match ADMINS.may_load(storage, admin)? {
+            Some(weight) => weight,
+            None => return Ok(None),
+        };
+        settle_rewards(storage, admin, weight)?;
+
+        ADMINS.remove(storage, admin, height)?;
+        clear_expiry(storage, admin)?;
+        TOTAL_WEIGHT.update(storage, height, |total| -> StdResult<_> {
+            Ok(total.unwrap_or_default() - weight)
+        })?;
+        ADMIN_COUNT.update(storage, |count| -> StdResult<_> { Ok(count - 1) })?;
+
+        // Pending removal requests die with the membership they targeted.
+        let approvers = REMOVAL_APPROVALS
+            .prefix(admin)
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for approver in approvers {
+            REMOVAL_APPROVALS.remove(storage, (admin, &approver));
+        }
+
+        Ok(Some(MemberDiff::new(admin, Some(weight), None)))
+    }
+
+    /// Moves whatever `admin` accrued with `weight` since the last settlement into
+    /// its pending rewards. Must run before every change of the admin's weight.
+    fn settle_rewards(storage: &mut dyn Storage, admin: &Addr, weight: u64) -> StdResult<()> {
+        let indexes = REWARD_INDEX
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        for (denom, index) in indexes {
+            let mut rewards = ADMIN_REWARDS
+                .may_load(storage, (admin, &denom))?
+                .unwrap_or_default();
+            rewards.pending += (index - rewards.index) * Decimal256::from_ratio(weight, 1u64);
+            rewards.index = index;
+            ADMIN_REWARDS.save(storage, (admin, &denom), &rewards)?;
+        }
+
+        Ok(())
+    }
+
+    fn ensure_admin(deps: Deps, sender: &Addr) -> Result<(), ContractError> {
+        if ADMINS.may_load(deps.storage, sender)?.is_none() {
             return Err(ContractError::Unauthorized {
-                sender: info.sender,
+                sender: sender.clone(),
             });
         }
 
-        let events = admins
-            .iter()
-            .map(|admin| Event::new("admin_added").add_attribute("addr", admin));
-        let resp = Response::new()
-            .add_events(events)
-            .add_attribute("action", "add_members")
-            .add_attribute("added_count", admins.len().to_string());
-        let admins: StdResult<Vec<_>> = admins
-            .into_iter()
-            .map(|addr| deps.api.addr_validate(&addr))
-            .collect();
+        Ok(())
+    }
 
-        curr_admins.append(&mut admins?);
-        ADMINS.save(deps.storage, &curr_admins)?;
+    pub fn has_role(deps: Deps, addr: &Addr, role: Role) -> StdResult<bool> {
+        match role {
+            Role::Member => Ok(ADMINS.may_load(deps.storage, addr)?.is_some()),
+            role => Ok(ROLES.has(deps.storage, (addr, role.as_str()))),
+        }
+    }
+
+    /// Fails unless `sender` holds `role` or is an owner.
+    fn ensure_role(deps: Deps, sender: &Addr, role: Role) -> Result<(), ContractError> {
+        if !has_role(deps, sender, role)? && !has_role(deps, sender, Role::Owner)? {
+            return Err(ContractError::Unauthorized {
+                sender: sender.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    pub fn grant_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::Owner)?;
+        if role == Role::Member {
+            return Err(ContractError::DerivedRole { role });
+        }
+        let addr = deps.api.addr_validate(&addr)?;
+        ROLES.save(deps.storage, (&addr, role.as_str()), &Empty {})?;
+
+        let resp = Response::new()
+            .add_attribute("action", "grant_role")
+            .add_event(RoleGranted { addr, role }.into());
 
         Ok(resp)
     }
 
-    pub fn leave(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-        ADMINS.update(deps.storage, move |admins| -> StdResult<_> {
-            let admins = admins
-                .into_iter()
-                .filter(|admin| *admin != info.sender)
-                .collect();
-            Ok(admins)
-        })?;
+    pub fn revoke_role(
+        deps: DepsMut,
+        info: MessageInfo,
+        addr: String,
+        role: Role,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::Owner)?;
+        if role == Role::Member {
+            return Err(ContractError::DerivedRole { role });
+        }
+        let addr = deps.api.addr_validate(&addr)?;
+        if role == Role::Owner && ROLES.has(deps.storage, (&addr, role.as_str())) {
+            let owners = ROLES
+                .keys(deps.storage, None, None, Order::Ascending)
+                .filter(|key| !matches!(key, Ok((_, role)) if role != Role::Owner.as_str()))
+                .take(2)
+                .count();
+            if owners < 2 {
+                return Err(ContractError::LastOwner {});
+            }
+        }
+        ROLES.remove(deps.storage, (&addr, role.as_str()));
 
-        Ok(Response::new())
+        let resp = Response::new()
+            .add_attribute("action", "revoke_role")
+            .add_event(RoleRevoked { addr, role }.into());
+
+        Ok(resp)
     }
 
-    pub fn donate(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-        let denom = DONATION_DENOM.load(deps.storage)?;
-        let admins = ADMINS.load(deps.storage)?;
+    pub fn add_members(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<Member>,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::MembershipManager)?;
+        if VOTING.exists(deps.storage) {
+            return Err(ContractError::ProposalRequired {});
+        }
+        let addrs = validate_new_members(deps.as_ref(), &env, &admins)?;
+
+        let mut events = vec![];
+        let mut diffs = vec![];
+        for (admin, member) in addrs.into_iter().zip(admins) {
+            let diff = add_admin(deps.storage, &admin, member.weight, env.block.height)?;
+            set_expiry(deps.storage, &admin, member.expires)?;
+            if diff.old.is_none() {
+                events.push(AdminAdded { addr: admin }.into());
+            }
+            diffs.push(diff);
+        }
 
-        let donation = cw_utils::must_pay(&info, &denom)?.u128();
-        let donation_per_admin = donation / (admins.len() as u128);
+        let resp = Response::new()
+            .add_attribute("action", "add_members")
+            .add_attribute("added_count", events.len().to_string())
+            .add_events(events)
+            .add_submessages(hook_messages(deps.storage, diffs)?);
 
-        let messages = admins.into_iter().map(|admin| BankMsg::Send {
-            to_address: admin.to_string(),
-            amount: coins(donation_per_admin, &denom),
-        });
+        Ok(resp)
+    }
+
+    pub fn renew_membership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        addr: String,
+        expires: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::MembershipManager)?;
+        let admin = deps.api.addr_validate(&addr)?;
+        if ADMINS.may_load(deps.storage, &admin)?.is_none() {
+            return Err(ContractError::NotAdmin { addr: admin });
+        }
+        validate_expiry(&env, expires)?;
+        set_expiry(deps.storage, &admin, expires)?;
 
         let resp = Response::new()
-            .add_messages(messages)
-            .add_attribute("action", "donate")
-            .add_attribute("amount", donation.to_string())
-            .add_attribute("per_admin", donation_per_admin.to_string());
+            .add_attribute("action", "renew_membership")
+            .add_attribute("addr", admin)
+            .add_attribute("expires", expires.unwrap_or_default().to_string());
 
         Ok(resp)
     }
-}
 
-// query
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    use QueryMsg::*;
+    pub fn update_weights(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<Member>,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::MembershipManager)?;
 
-    match msg {
-        Greet {} => to_binary(&query::greet()?),
-        AdminsList {} => to_binary(&query::admins_list(deps)?),
+        let mut diffs = vec![];
+        for member in &admins {
+            let admin = deps.api.addr_validate(&member.addr)?;
+            if ADMINS.may_load(deps.storage, &admin)?.is_none() {
+                return Err(ContractError::NotAdmin { addr: admin });
+            }
+            diffs.push(add_admin(
+                deps.storage,
+                &admin,
+                member.weight,
+                env.block.height,
+            )?);
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "update_weights")
+            .add_attribute("updated_count", admins.len().to_string())
+            .add_submessages(hook_messages(deps.storage, diffs)?);
+
+        Ok(resp)
     }
-}
 
-mod query {
-    use crate::msg::AdminsListResp;
+    /// Fails if removing one more admin would leave fewer than `MIN_ADMINS`.
+    fn ensure_above_minimum(deps: Deps) -> Result<(), ContractError> {
+        let min = MIN_ADMINS.load(deps.storage)?;
+        if ADMIN_COUNT.load(deps.storage)? <= min {
+            return Err(ContractError::TooFewAdmins { min });
+        }
 
-    use super::*;
+        Ok(())
+    }
 
-    pub fn greet() -> StdResult<GreetResp> {
-        let resp = GreetResp {
-            message: "Hello World".to_owned(),
-        };
+    pub fn leave(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if ADMINS.may_load(deps.storage, &info.sender)?.is_some() {
+            ensure_above_minimum(deps.as_ref())?;
+        }
+        let diffs = remove_admin(deps.storage, &info.sender, env.block.height)?
+            .into_iter()
+            .collect();
+
+        let resp = Response::new().add_submessages(hook_messages(deps.storage, diffs)?);
 
         Ok(resp)
     }
 
-    pub fn admins_list(deps: Deps) -> StdResult<AdminsListResp> {
-        let admins = ADMINS.load(deps.storage)?;
-        let resp = AdminsListResp { admins };
+    pub fn reseed(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<Member>,
+    ) -> Result<Response, ContractError> {
+        let guardian = GUARDIAN.may_load(deps.storage)?;
+        let wasm_admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin;
+        if guardian.as_ref() != Some(&info.sender)
+            && wasm_admin.as_deref() != Some(info.sender.as_str())
+        {
+            return Err(ContractError::Unauthorized {
+                sender: info.sender,
+            });
+        }
+        if ADMIN_COUNT.load(deps.storage)? > 0 {
+            return Err(ContractError::GroupNotEmpty {});
+        }
+        let min = MIN_ADMINS.load(deps.storage)?;
+        if (admins.len() as u64) < min.max(1) {
+            return Err(ContractError::TooFewAdmins { min: min.max(1) });
+        }
+
+        let addrs = validate_new_members(deps.as_ref(), &env, &admins)?;
+
+        let mut events = vec![];
+        let mut diffs = vec![];
+        for (admin, member) in addrs.into_iter().zip(admins) {
+            diffs.push(add_admin(
+                deps.storage,
+                &admin,
+                member.weight,
+                env.block.height,
+            )?);
+            set_expiry(deps.storage, &admin, member.expires)?;
+            events.push(AdminAdded { addr: admin }.into());
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "reseed")
+            .add_attribute("added_count", events.len().to_string())
+            .add_events(events)
+            .add_submessages(hook_messages(deps.storage, diffs)?);
 
         Ok(resp)
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::msg::AdminsListResp;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary, Addr};
-    use cw_multi_test::{App, ContractWrapper, Executor};
-    use std::vec;
+    pub fn remove_members(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        admins: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        if VOTING.exists(deps.storage) {
+            return Err(ContractError::ProposalRequired {});
+        }
 
-    #[test]
-    fn donations() {
-        let mut app = App::new(|router, _, storage| {
-            router
-                .bank
-                .init_balance(storage, &Addr::unchecked("user"), coins(5, "eth"))
-                .unwrap()
-        });
+        let targets = admins
+            .iter()
+            .map(|addr| deps.api.addr_validate(addr))
+            .collect::<StdResult<Vec<_>>>()?;
+        for target in &targets {
+            if ADMINS.may_load(deps.storage, target)?.is_none() {
+                return Err(ContractError::NotAdmin {
+                    addr: target.clone(),
+                });
+            }
+        }
 
-        let code = ContractWrapper::new(execute, instantiate, query);
-        let code_id = app.store_code(Box::new(code));
+        let policy = REMOVAL_POLICY.load(deps.storage)?;
+        let mut events: Vec<Event> = vec![];
+        let mut diffs = vec![];
+        for target in targets {
+            if policy != RemovalPolicy::AnyAdmin {
+                REMOVAL_APPROVALS.save(deps.storage, (&target, &info.sender), &Empty {})?;
+                let approvals = removal_approvals(deps.as_ref(), &target, policy)?;
+                let admin_count = ADMIN_COUNT.load(deps.storage)?;
+                let required = match policy {
+                    RemovalPolicy::AnyAdmin => 1,
+                    RemovalPolicy::Majority => admin_count / 2 + 1,
+                    RemovalPolicy::AllButTarget => admin_count - 1,
+                };
+                if approvals < required {
+                    events.push(
+                        RemovalApproved {
+                            addr: target,
+                            approver: info.sender.clone(),
+                            approvals,
+                            required,
+                        }
+                        .into(),
+                    );
+                    continue;
+                }
+            }
 
-        let addr = app
-            .instantiate_contract(
-                code_id,
-                Addr::unchecked("owner"),
-                &InstantiateMsg {
-                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
-                    donation_denom: "eth".to_owned(),
-                },
-                &[],
-                "Contract",
-                None,
-            )
-            .unwrap();
+            ensure_above_minimum(deps.as_ref())?;
+            if let Some(diff) = remove_admin(deps.storage, &target, env.block.height)? {
+                events.push(AdminRemoved { addr: target }.into());
+                diffs.push(diff);
+            }
+        }
 
-        app.execute_contract(
-            Addr::unchecked("user"),
-            addr.clone(),
-            &ExecuteMsg::Donate {},
-            &coins(5, "eth"),
-        )
-        .unwrap();
+        let resp = Response::new()
+            .add_events(events)
+            .add_attribute("action", "remove_members")
+            .add_attribute("removed_count", diffs.len().to_string())
+            .add_submessages(hook_messages(deps.storage, diffs)?);
 
-        assert_eq!(
-            app.wrap()
-                .query_balance("user", "eth")
-                .unwrap()
-                .amount
-                .u128(),
-            0
+        Ok(resp)
+    }
+
+    /// Counts the current admins asking to remove `target` under `policy`.
+    fn removal_approvals(deps: Deps, target: &Addr, policy: RemovalPolicy) -> StdResult<u64> {
+        let mut approvals = 0;
+        for approver in
+            REMOVAL_APPROVALS
+                .prefix(target)
+                .keys(deps.storage, None, None, Order::Ascending)
+        {
+            let approver = approver?;
+            if policy == RemovalPolicy::AllButTarget && approver == target {
+                continue;
+            }
+            if ADMINS.may_load(deps.storage, &approver)?.is_some() {
+                approvals += 1;
+            }
+        }
+
+        Ok(approvals)
+    }
+
+    pub fn donate(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+        if info.funds.is_empty() {
+            return Err(PaymentError::NoFunds {}.into());
+        }
+
+        for coin in &info.funds {
+            if !DONATION_DENOMS.has(deps.storage, &coin.denom) {
+                return Err(ContractError::UnsupportedDenom {
+                    denom: coin.denom.clone(),
+                });
+            }
+        }
+
+        split_donation(deps, &env, &info.sender, &info.funds)
+    }
+
+    /// Handles cw20 `Send`, where `info.sender` is the token contract.
+    pub fn receive(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        wrapper: Cw20ReceiveMsg,
+    ) -> Result<Response, ContractError> {
+        if !CW20_TOKENS.has(deps.storage, &info.sender) {
+            return Err(ContractError::UnsupportedToken { token: info.sender });
+        }
+
+        match from_binary(&wrapper.msg)? {
+            ReceiveMsg::Donate {} => {
+                if wrapper.amount.is_zero() {
+                    return Err(PaymentError::NoFunds {}.into());
+                }
+                let funds = vec![Coin {
+                    denom: cw20_denom(&info.sender),
+                    amount: wrapper.amount,
+                }];
+
+                let donor = deps.api.addr_validate(&wrapper.sender)?;
+                let resp = split_donation(deps, &env, &donor, &funds)?
+                    .add_attribute("sender", wrapper.sender);
+                Ok(resp)
+            }
+        }
+    }
+
+    /// cw20 balances are tracked next to native ones under a prefixed pseudo-denom.
+    fn cw20_denom(token: &Addr) -> String {
+        format!("{CW20_PREFIX}{token}")
+    }
+
+    /// Builds the messages paying out `amount`, which may mix native and cw20 coins.
+    fn payout(to: &Addr, amount: Vec<Coin>) -> StdResult<Vec<CosmosMsg>> {
+        let (tokens, native): (Vec<_>, Vec<_>) = amount
+            .into_iter()
+            .partition(|coin| coin.denom.starts_with(CW20_PREFIX));
+
+        let mut messages = vec![];
+        if !native.is_empty() {
+            messages.push(
+                BankMsg::Send {
+                    to_address: to.to_string(),
+                    amount: native,
+                }
+                .into(),
+            );
+        }
+        for coin in tokens {
+            messages.push(
+                WasmMsg::Execute {
+                    contract_addr: coin.denom[CW20_PREFIX.len()..].to_owned(),
+                    msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                        recipient: to.to_string(),
+                        amount: coin.amount,
+                    })?,
+                    funds: vec![],
+                }
+                .into(),
+            );
+        }
+
+        Ok(messages)
+    }
+
+    fn split_donation(
+        deps: DepsMut,
+        env: &Env,
+        donor: &Addr,
+        funds: &[Coin],
+    ) -> Result<Response, ContractError> {
+        let total_weight = TOTAL_WEIGHT.load(deps.storage)?;
+        if total_weight == 0 {
+            return Err(ContractError::NoAdmins {});
+        }
+
+        // Contracts instantiated before the ledger existed start it here.
+        let id = DONATION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        DONATION_COUNT.save(deps.storage, &id)?;
+        let (resp, splits) = match PAYOUT_MODE.load(deps.storage)? {
+            PayoutMode::Push => {
+                let (splits, dust) = split_shares(deps.storage, funds, total_weight)?;
+                let mut messages = vec![];
+                for split in &splits {
+                    messages.extend(payout(&split.recipient, split.coins.clone())?);
+                }
+                let resp = Response::new()
+                    .add_messages(messages)
+                    .add_attribute("dust", coins_to_string(&dust));
+                (resp, splits)
+            }
+            PayoutMode::Pull => (accrue(deps.storage, funds, total_weight)?, vec![]),
+            PayoutMode::Vesting { cliff, duration } => {
+                let (splits, dust) = split_shares(deps.storage, funds, total_weight)?;
+                for split in &splits {
+                    let stream = VestingStream {
+                        start: env.block.time,
+                        cliff,
+                        duration,
+                        total: split.coins.clone(),
+                        withdrawn: vec![Uint128::zero(); split.coins.len()],
+                    };
+                    VESTING.save(deps.storage, (&split.recipient, id), &stream)?;
+                }
+                let resp = Response::new().add_attribute("dust", coins_to_string(&dust));
+                (resp, splits)
+            }
+        };
+
+        let record = DonationRecord {
+            donor: donor.clone(),
+            coins: funds.to_vec(),
+            height: env.block.height,
+            time: env.block.time,
+            splits,
+        };
+        donations().save(deps.storage, id, &record)?;
+
+        let resp = resp
+            .add_attribute("action", "donate")
+            .add_attribute("donation_id", id.to_string())
+            .add_attribute("amount", coins_to_string(funds));
+
+        Ok(resp)
+    }
+
+    /// Splits `funds` between the admins by weight, returning every admin's
+    /// share and the dust rolled over to the next donation.
+    fn split_shares(
+        storage: &mut dyn Storage,
+        funds: &[Coin],
+        total_weight: u64,
+    ) -> Result<(Vec<DonationSplit>, Vec<Coin>), ContractError> {
+        // Whatever could not be split evenly last time goes into this distribution.
+        let mut distributable = vec![];
+        for coin in funds {
+            let dust = DUST.may_load(storage, &coin.denom)?.unwrap_or_default();
+            distributable.push(Coin {
+                denom: coin.denom.clone(),
+                amount: coin.amount + dust,
+            });
+        }
+
+        let mut splits = vec![];
+        let mut distributed = vec![Uint128::zero(); distributable.len()];
+        for admin in ADMINS.range(storage, None, None, Order::Ascending) {
+            let (admin, weight) = admin?;
+            let share = Decimal::from_ratio(weight, total_weight);
+
+            let mut amount = vec![];
+            for (coin, distributed) in distributable.iter().zip(&mut distributed) {
+                let part = coin.amount * share;
+                if part.is_zero() {
+                    continue;
+                }
+
+                *distributed += part;
+                amount.push(Coin {
+                    denom: coin.denom.clone(),
+                    amount: part,
+                });
+            }
+
+            if !amount.is_empty() {
+                splits.push(DonationSplit {
+                    recipient: admin,
+                    coins: amount,
+                });
+            }
+        }
+
+        let mut dust = vec![];
+        for (coin, distributed) in distributable.into_iter().zip(distributed) {
+            let remainder = coin.amount - distributed;
+            DUST.save(storage, &coin.denom, &remainder)?;
+            dust.push(Coin {
+                denom: coin.denom,
+                amount: remainder,
+            });
+        }
+
+        Ok((splits, dust))
+    }
+
+    /// Bumps the per-weight reward index, leaving admins to `Claim` their share.
+    fn accrue(
+        storage: &mut dyn Storage,
+        funds: &[Coin],
+        total_weight: u64,
+    ) -> Result<Response, ContractError> {
+        for coin in funds {
+            let increase = Decimal256::from_ratio(coin.amount, total_weight);
+            REWARD_INDEX.update(storage, &coin.denom, |index| -> StdResult<_> {
+                Ok(index.unwrap_or_default() + increase)
+            })?;
+        }
+
+        Ok(Response::new())
+    }
+
+    pub fn withdraw_vested(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> Result<Response, ContractError> {
+        // Every donation adds a stream, so a single call only walks one page of them.
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let streams = VESTING
+            .prefix(&info.sender)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+            .collect::<StdResult<Vec<_>>>()?;
+        let last_stream = streams.last().map(|(id, _)| *id);
+
+        let mut amount: Vec<Coin> = vec![];
+        for (id, mut stream) in streams {
+            let vested = stream.vested(env.block.time);
+            for ((coin, withdrawn), vested) in
+                stream.total.iter().zip(&mut stream.withdrawn).zip(vested)
+            {
+                let released = vested - *withdrawn;
+                if released.is_zero() {
+                    continue;
+                }
+                *withdrawn = vested;
+                add_coin(&mut amount, &coin.denom, released);
+            }
+
+            let done = stream
+                .total
+                .iter()
+                .zip(&stream.withdrawn)
+                .all(|(coin, withdrawn)| coin.amount == *withdrawn);
+            if done {
+                VESTING.remove(deps.storage, (&info.sender, id));
+            } else {
+                VESTING.save(deps.storage, (&info.sender, id), &stream)?;
+            }
+        }
+
+        if amount.is_empty() {
+            return Err(ContractError::NothingToClaim { addr: info.sender });
+        }
+
+        let mut resp = Response::new()
+            .add_attribute("action", "withdraw_vested")
+            .add_attribute("amount", coins_to_string(&amount));
+        if let Some(last_stream) = last_stream {
+            resp = resp.add_attribute("last_stream", last_stream.to_string());
+        }
+        let resp = resp.add_messages(payout(&info.sender, amount)?);
+
+        Ok(resp)
+    }
+
+    /// Adds `amount` of `denom` into `coins`, merging it with a coin of the same denom.
+    pub fn add_coin(coins: &mut Vec<Coin>, denom: &str, amount: Uint128) {
+        if amount.is_zero() {
+            return;
+        }
+        match coins.iter_mut().find(|coin| coin.denom == denom) {
+            Some(coin) => coin.amount += amount,
+            None => coins.push(Coin {
+                denom: denom.to_owned(),
+                amount,
+            }),
+        }
+    }
+
+    pub fn claim(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        let weight = ADMINS
+            .may_load(deps.storage, &info.sender)?
+            .unwrap_or_default();
+        settle_rewards(deps.storage, &info.sender, weight)?;
+
+        let rewards = ADMIN_REWARDS
+            .prefix(&info.sender)
+            .range(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut amount = vec![];
+        for (denom, mut reward) in rewards {
+            let claimed = reward.pending.floor();
+            if claimed.is_zero() {
+                continue;
+            }
+
+            reward.pending -= claimed;
+            ADMIN_REWARDS.save(deps.storage, (&info.sender, &denom), &reward)?;
+            amount.push(Coin {
+                denom,
+                amount: Uint128::try_from(claimed.to_uint_floor()).map_err(StdError::from)?,
+            });
+        }
+
+        if amount.is_empty() {
+            return Err(ContractError::NothingToClaim { addr: info.sender });
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "claim")
+            .add_attribute("amount", coins_to_string(&amount))
+            .add_messages(payout(&info.sender, amount)?);
+
+        Ok(resp)
+    }
+
+    fn coins_to_string(coins: &[Coin]) -> String {
+        coins
+            .iter()
+            .map(Coin::to_string)
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    pub fn update_denoms(
+        deps: DepsMut,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+
+        for denom in &add {
+            DONATION_DENOMS.save(deps.storage, denom, &Empty {})?;
+        }
+        for denom in &remove {
+            DONATION_DENOMS.remove(deps.storage, denom);
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "update_denoms")
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn update_cw20_tokens(
+        deps: DepsMut,
+        info: MessageInfo,
+        add: Vec<String>,
+        remove: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        ensure_role(deps.as_ref(), &info.sender, Role::Treasurer)?;
+
+        for token in &add {
+            let token = deps.api.addr_validate(token)?;
+            CW20_TOKENS.save(deps.storage, &token, &Empty {})?;
+        }
+        for token in &remove {
+            let token = deps.api.addr_validate(token)?;
+            CW20_TOKENS.remove(deps.storage, &token);
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "update_cw20_tokens")
+            .add_attribute("added", add.join(","))
+            .add_attribute("removed", remove.join(","));
+
+        Ok(resp)
+    }
+
+    pub fn propose(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        action: ProposalAction,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+        let voting = VOTING
+            .may_load(deps.storage)?
+            .ok_or(ContractError::VotingDisabled {})?;
+
+        match &action {
+            ProposalAction::AddMembers { admins } => {
+                validate_new_members(deps.as_ref(), &env, admins)?;
+            }
+            ProposalAction::RemoveMembers { admins } => {
+                for addr in admins {
+                    deps.api.addr_validate(addr)?;
+                }
+            }
+        }
+
+        let id = PROPOSAL_COUNT.update(deps.storage, |count| -> StdResult<_> { Ok(count + 1) })?;
+        let mut proposal = Proposal {
+            proposer: info.sender.clone(),
+            action,
+            status: ProposalStatus::Open,
+            expires: voting.max_voting_period.after(&env.block),
+            start_height: env.block.height,
+            threshold: voting.threshold,
+            total_admins: ADMIN_COUNT.load(deps.storage)?,
+            yes: 0,
+            no: 0,
+        };
+
+        let resp = Response::new()
+            .add_attribute("action", "propose")
+            .add_attribute("proposal_id", id.to_string());
+        let resp = cast_vote(
+            deps,
+            &env,
+            id,
+            &mut proposal,
+            &info.sender,
+            VoteOption::Yes,
+            resp,
+        )?;
+
+        Ok(resp)
+    }
+
+    pub fn vote(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        proposal_id: u64,
+        vote: VoteOption,
+    ) -> Result<Response, ContractError> {
+        ensure_admin(deps.as_ref(), &info.sender)?;
+
+        let mut proposal = PROPOSALS
+            .may_load(deps.storage, proposal_id)?
+            .ok_or(ContractError::ProposalNotFound { id: proposal_id })?;
+        if proposal.status != ProposalStatus::Open {
+            return Err(ContractError::ProposalNotOpen { id: proposal_id });
+        }
+        if proposal.expires.is_expired(&env.block) {
+            return Err(ContractError::ProposalExpired { id: proposal_id });
+        }
+        if VOTES.has(deps.storage, (proposal_id, &info.sender)) {
+            return Err(ContractError::AlreadyVoted {
+                id: proposal_id,
+                voter: info.sender,
+            });
+        }
+        // `total_admins` is frozen at creation, so later admins must not add to the tally.
+        if ADMINS
+            .may_load_at_height(deps.storage, &info.sender, proposal.start_height)?
+            .is_none()
+        {
+            return Err(ContractError::NotEligible {
+                id: proposal_id,
+                voter: info.sender,
+            });
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "vote")
+            .add_attribute("proposal_id", proposal_id.to_string());
+        let resp = cast_vote(
+            deps,
+            &env,
+            proposal_id,
+            &mut proposal,
+            &info.sender,
+            vote,
+            resp,
+        )?;
+
+        Ok(resp)
+    }
+
+    /// Records the vote, then executes or rejects the proposal once the outcome is settled.
+    fn cast_vote(
+        mut deps: DepsMut,
+        env: &Env,
+        id: u64,
+        proposal: &mut Proposal,
+        voter: &Addr,
+        vote: VoteOption,
+        resp: Response,
+    ) -> Result<Response, ContractError> {
+        VOTES.save(deps.storage, (id, voter), &vote)?;
+        match vote {
+            VoteOption::Yes => proposal.yes += 1,
+            VoteOption::No => proposal.no += 1,
+        }
+
+        let undecided = proposal
+            .total_admins
+            .saturating_sub(proposal.yes + proposal.no);
+        let mut resp = resp;
+        if threshold_met(&proposal.threshold, proposal.yes, proposal.total_admins) {
+            proposal.status = ProposalStatus::Executed;
+            let (events, diffs) = apply_action(deps.branch(), env, &proposal.action)?;
+            resp = resp
+                .add_events(events)
+                .add_submessages(hook_messages(deps.storage, diffs)?);
+        } else if !threshold_met(
+            &proposal.threshold,
+            proposal.yes + undecided,
+            proposal.total_admins,
+        ) {
+            proposal.status = ProposalStatus::Rejected;
+        }
+
+        PROPOSALS.save(deps.storage, id, proposal)?;
+
+        Ok(resp.add_attribute("status", proposal.status.to_string()))
+    }
+
+    fn threshold_met(threshold: &Threshold, yes: u64, total: u64) -> bool {
+        match threshold {
+            Threshold::AbsoluteCount { count } => yes >= *count,
+            Threshold::Percentage { percentage } => {
+                total > 0 && Decimal::from_ratio(yes, total) >= *percentage
+            }
+        }
+    }
+
+    fn apply_action(
+        deps: DepsMut,
+        env: &Env,
+        action: &ProposalAction,
+    ) -> Result<(Vec<Event>, Vec<MemberDiff>), ContractError> {
+        let mut events = vec![];
+        let mut diffs = vec![];
+        match action {
+            ProposalAction::AddMembers { admins } => {
+                for member in admins {
+                    // Whoever joined while the proposal was open keeps their membership.
+                    let admin = deps.api.addr_validate(&member.addr)?;
+                    if ADMINS.may_load(deps.storage, &admin)?.is_some() {
+                        continue;
+                    }
+                    diffs.push(add_admin(
+                        deps.storage,
+                        &admin,
+                        member.weight,
+                        env.block.height,
+                    )?);
+                    set_expiry(deps.storage, &admin, member.expires)?;
+                    events.push(AdminAdded { addr: admin }.into());
+                }
+            }
+            ProposalAction::RemoveMembers { admins } => {
+                for addr in admins {
+                    let admin = deps.api.addr_validate(addr)?;
+                    if ADMINS.may_load(deps.storage, &admin)?.is_some() {
+                        ensure_above_minimum(deps.as_ref())?;
+                    }
+                    if let Some(diff) = remove_admin(deps.storage, &admin, env.block.height)? {
+                        events.push(AdminRemoved { addr: admin }.into());
+                        diffs.push(diff);
+                    }
+                }
+            }
+        }
+
+        Ok((events, diffs))
+    }
+}
+
+// sudo
+pub fn sudo(mut deps: DepsMut, env: Env, msg: SudoMsg) -> Result<Response, ContractError> {
+    use SudoMsg::*;
+
+    let (expired, diffs) = exec::prune_expired(deps.branch(), &env)?;
+    let hooks = exec::hook_messages(deps.storage, diffs)?;
+
+    let resp = match msg {
+        ReplaceAdmins { admins } => sudo::replace_admins(deps, env, admins),
+        SetDonationDenoms { denoms } => sudo::set_donation_denoms(deps, denoms),
+    }?;
+
+    Ok(resp.add_events(expired).add_submessages(hooks))
+}
+
+mod sudo {
+    use super::*;
+    use crate::events::{AdminAdded, AdminRemoved};
+    use crate::msg::Member;
+
+    pub fn replace_admins(
+        deps: DepsMut,
+        env: Env,
+        admins: Vec<Member>,
+    ) -> Result<Response, ContractError> {
+        let new_admins = admins
+            .into_iter()
+            .map(|member| {
+                let addr = deps.api.addr_validate(&member.addr)?;
+                Ok((addr, member.weight, member.expires))
+            })
+            .collect::<StdResult<Vec<_>>>()?;
+        let old_admins = ADMINS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let mut events = vec![];
+        let mut diffs = vec![];
+        for admin in old_admins {
+            if new_admins.iter().all(|(addr, _, _)| *addr != admin) {
+                diffs.extend(exec::remove_admin(deps.storage, &admin, env.block.height)?);
+                events.push(AdminRemoved { addr: admin }.into());
+            }
+        }
+        for (admin, weight, expires) in &new_admins {
+            let diff = exec::add_admin(deps.storage, admin, *weight, env.block.height)?;
+            exec::set_expiry(deps.storage, admin, *expires)?;
+            if diff.old.is_none() {
+                events.push(
+                    AdminAdded {
+                        addr: admin.clone(),
+                    }
+                    .into(),
+                );
+            }
+            if diff.old != diff.new {
+                diffs.push(diff);
+            }
+        }
+
+        let resp = Response::new()
+            .add_events(events)
+            .add_attribute("action", "sudo_replace_admins")
+            .add_attribute("admin_count", new_admins.len().to_string())
+            .add_submessages(exec::hook_messages(deps.storage, diffs)?);
+
+        Ok(resp)
+    }
+
+    pub fn set_donation_denoms(
+        deps: DepsMut,
+        denoms: Vec<String>,
+    ) -> Result<Response, ContractError> {
+        DONATION_DENOMS.clear(deps.storage);
+        for denom in &denoms {
+            DONATION_DENOMS.save(deps.storage, denom, &Empty {})?;
+        }
+
+        let resp = Response::new()
+            .add_attribute("action", "sudo_set_donation_denoms")
+            .add_attribute("denoms", denoms.join(","));
+
+        Ok(resp)
+    }
+}
+
+// migrate
+pub fn migrate(deps: DepsMut, env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    // The first version stored no cw2 info, so a missing entry means that version.
+    let stored = cw2::CONTRACT
+        .may_load(deps.storage)?
+        .unwrap_or_else(|| ContractVersion {
+            contract: CONTRACT_NAME.to_owned(),
+            version: "0.0.0".to_owned(),
+        });
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_owned(),
+            actual: stored.contract,
+        });
+    }
+
+    let parse = |version: &str| {
+        version
+            .parse::<semver::Version>()
+            .map_err(|err| ContractError::InvalidVersion {
+                version: version.to_owned(),
+                reason: err.to_string(),
+            })
+    };
+    if parse(&stored.version)? > parse(CONTRACT_VERSION)? {
+        return Err(ContractError::Downgrade {
+            stored: stored.version,
+            current: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    move_weights_to_cw4_keys(deps.storage)?;
+
+    // The first version kept a plain admin list, every admin weighing the same.
+    if let Some(admins) = LEGACY_ADMINS.may_load(deps.storage)? {
+        if ADMIN_COUNT.may_load(deps.storage)?.is_none() {
+            ADMIN_COUNT.save(deps.storage, &0)?;
+        }
+        for admin in admins {
+            // Its AddMembers never rejected an address that was already listed.
+            if ADMINS.may_load(deps.storage, &admin)?.is_none() {
+                exec::add_admin(deps.storage, &admin, 1, env.block.height)?;
+            }
+        }
+        LEGACY_ADMINS.remove(deps.storage);
+    }
+    if TOTAL_WEIGHT.may_load(deps.storage)?.is_none() {
+        TOTAL_WEIGHT.save(deps.storage, &0, env.block.height)?;
+    }
+    if let Some(denom) = LEGACY_DONATION_DENOM.may_load(deps.storage)? {
+        DONATION_DENOMS.save(deps.storage, &denom, &Empty {})?;
+        LEGACY_DONATION_DENOM.remove(deps.storage);
+    }
+
+    // Instances deployed before roles existed get the wasm admin running this
+    // migration as their owner, or nobody could manage the group anymore.
+    let roles = ROLES
+        .keys(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    if !roles.iter().any(|(_, role)| role == Role::Owner.as_str()) {
+        let wasm_admin = deps
+            .querier
+            .query_wasm_contract_info(&env.contract.address)?
+            .admin;
+        if let Some(owner) = wasm_admin {
+            let owner = deps.api.addr_validate(&owner)?;
+            ROLES.save(deps.storage, (&owner, Role::Owner.as_str()), &Empty {})?;
+        }
+    }
+
+    // Settings added after an instance was deployed take their instantiate defaults.
+    if PAYOUT_MODE.may_load(deps.storage)?.is_none() {
+        PAYOUT_MODE.save(deps.storage, &PayoutMode::default())?;
+    }
+    if REMOVAL_POLICY.may_load(deps.storage)?.is_none() {
+        REMOVAL_POLICY.save(deps.storage, &RemovalPolicy::default())?;
+    }
+    if MIN_ADMINS.may_load(deps.storage)?.is_none() {
+        MIN_ADMINS.save(deps.storage, &1)?;
+    }
+    if PROPOSAL_COUNT.may_load(deps.storage)?.is_none() {
+        PROPOSAL_COUNT.save(deps.storage, &0)?;
+    }
+    if DONATION_COUNT.may_load(deps.storage)?.is_none() {
+        DONATION_COUNT.save(deps.storage, &0)?;
+    }
+
+    // Expiries stored before the expiry queues existed have to be queued once.
+    let expiries = ADMIN_EXPIRY
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (admin, expires) in expiries {
+        exec::queue_expiry(deps.storage, &admin, &expires)?;
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    Ok(resp)
+}
+
+/// Moves the admin weights, their total and their history from the keys used
+/// before the cw4 ones.
+fn move_weights_to_cw4_keys(storage: &mut dyn Storage) -> StdResult<()> {
+    // The current values are copied raw: saving them through the snapshots
+    // would record that nobody held any weight before this block.
+    let legacy_weights: Map<&Addr, u64> = Map::new("admins");
+    let weights: Map<&Addr, u64> = Map::new(cw4::MEMBERS_KEY);
+    let admins = legacy_weights
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (admin, weight) in admins {
+        weights.save(storage, &admin, &weight)?;
+        legacy_weights.remove(storage, &admin);
+    }
+    let changes = LEGACY_ADMIN_WEIGHTS
+        .changelog()
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((admin, height), change) in changes {
+        ADMINS
+            .changelog()
+            .save(storage, (&admin, height), &change)?;
+        LEGACY_ADMIN_WEIGHTS
+            .changelog()
+            .remove(storage, (&admin, height));
+    }
+
+    let legacy_total: Item<u64> = Item::new("total_weight");
+    if let Some(total) = legacy_total.may_load(storage)? {
+        Item::new(cw4::TOTAL_KEY).save(storage, &total)?;
+        legacy_total.remove(storage);
+    }
+    let changes = LEGACY_TOTAL_WEIGHT
+        .changelog()
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (height, change) in changes {
+        TOTAL_WEIGHT.changelog().save(storage, height, &change)?;
+        LEGACY_TOTAL_WEIGHT.changelog().remove(storage, height);
+    }
+
+    Ok(())
+}
+
+// query
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    use QueryMsg::*;
+
+    match msg {
+        Greet {} => to_binary(&query::greet()?),
+        ContractVersion {} => to_binary(&cw2::get_contract_version(deps.storage)?),
+        AdminsList { start_after, limit } => {
+            to_binary(&query::admins_list(deps, env, start_after, limit)?)
+        }
+        Proposal { proposal_id } => to_binary(&query::proposal(deps, env, proposal_id)?),
+        ListProposals { start_after, limit } => {
+            to_binary(&query::list_proposals(deps, env, start_after, limit)?)
+        }
+        ListVotes {
+            proposal_id,
+            start_after,
+            limit,
+        } => to_binary(&query::list_votes(deps, proposal_id, start_after, limit)?),
+        Dust {} => to_binary(&query::dust(deps)?),
+        DonationDenoms {} => to_binary(&query::donation_denoms(deps)?),
+        Cw20Tokens {} => to_binary(&query::cw20_tokens(deps)?),
+        PendingRewards { addr } => to_binary(&query::pending_rewards(deps, addr)?),
+        HasRole { addr, role } => to_binary(&query::has_role(deps, env, addr, role)?),
+        Hooks {} => to_binary(&query::hooks(deps)?),
+        Admin {} => to_binary(&query::admin(deps)?),
+        Member { addr, at_height } => to_binary(&query::member(deps, env, addr, at_height)?),
+        ListMembers { start_after, limit } => {
+            to_binary(&query::list_members(deps, env, start_after, limit)?)
+        }
+        TotalWeight { at_height } => to_binary(&query::total_weight(deps, env, at_height)?),
+        Vesting {
+            addr,
+            start_after,
+            limit,
+        } => to_binary(&query::vesting(deps, env, addr, start_after, limit)?),
+        DonationHistory {
+            donor,
+            min_height,
+            max_height,
+            start_after,
+            limit,
+        } => to_binary(&query::donation_history(
+            deps,
+            donor,
+            min_height,
+            max_height,
+            start_after,
+            limit,
+        )?),
+    }
+}
+
+mod query {
+    use cw_storage_plus::Bound;
+
+    use crate::msg::{
+        AdminResp, AdminsListResp, Cw20TokensResp, DonationDenomsResp, DonationHistoryResp,
+        DonationResp, DustResp, HasRoleResp, PendingRewardsResp, ProposalListResp, ProposalResp,
+        ProposalStatus, VestingResp, VoteListResp, VoteResp,
+    };
+    use crate::state::{
+        donations, DonationRecord, Proposal, ADMIN_EXPIRY, ADMIN_REWARDS, DUST, PROPOSALS,
+        REWARD_INDEX, VESTING, VOTES,
+    };
+    use cosmwasm_std::{Addr, Coin, Decimal, Decimal256, Uint128};
+
+    use super::*;
+
+    pub fn greet() -> StdResult<GreetResp> {
+        let resp = GreetResp {
+            message: "Hello World".to_owned(),
+        };
+
+        Ok(resp)
+    }
+
+    pub fn admins_list(
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<AdminsListResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let total_weight = live_total_weight(deps, &env)?;
+
+        let mut admins = vec![];
+        for item in ADMINS.range(deps.storage, start, None, Order::Ascending) {
+            if admins.len() == limit {
+                break;
+            }
+            let (addr, weight) = item?;
+            let expires = ADMIN_EXPIRY.may_load(deps.storage, &addr)?;
+            if matches!(expires, Some(expires) if expires.is_expired(&env.block)) {
+                continue;
+            }
+            admins.push(AdminResp {
+                share: if total_weight == 0 {
+                    Decimal::zero()
+                } else {
+                    Decimal::from_ratio(weight, total_weight)
+                },
+                addr,
+                weight,
+                expires,
+            });
+        }
+        let resp = AdminsListResp { admins };
+
+        Ok(resp)
+    }
+
+    /// Total weight of the admins whose membership has not lapsed. Lapsed admins
+    /// linger in storage until the next execute prunes them.
+    fn live_total_weight(deps: Deps, env: &Env) -> StdResult<u64> {
+        let mut total_weight = TOTAL_WEIGHT.load(deps.storage)?;
+        for admin in exec::lapsed_admins(deps.storage, &env.block)? {
+            total_weight -= ADMINS.load(deps.storage, &admin)?;
+        }
+
+        Ok(total_weight)
+    }
+
+    fn is_expired(deps: Deps, env: &Env, admin: &Addr) -> StdResult<bool> {
+        let expires = ADMIN_EXPIRY.may_load(deps.storage, admin)?;
+
+        Ok(matches!(expires, Some(expires) if expires.is_expired(&env.block)))
+    }
+
+    pub fn admin(deps: Deps) -> StdResult<cw4::AdminResponse> {
+        let admin = ROLES
+            .keys(deps.storage, None, None, Order::Ascending)
+            .find(|key| !matches!(key, Ok((_, role)) if role != Role::Owner.as_str()))
+            .transpose()?
+            .map(|(owner, _)| owner.into_string());
+
+        Ok(cw4::AdminResponse { admin })
+    }
+
+    /// Snapshots are taken as admins change, so a lapsed admin still counts at
+    /// past heights until the expiry gets pruned.
+    pub fn member(
+        deps: Deps,
+        env: Env,
+        addr: String,
+        at_height: Option<u64>,
+    ) -> StdResult<cw4::MemberResponse> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let weight = match at_height {
+            Some(height) => ADMINS.may_load_at_height(deps.storage, &addr, height)?,
+            None if is_expired(deps, &env, &addr)? => None,
+            None => ADMINS.may_load(deps.storage, &addr)?,
+        };
+
+        Ok(cw4::MemberResponse { weight })
+    }
+
+    pub fn list_members(
+        deps: Deps,
+        env: Env,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<cw4::MemberListResponse> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let mut members = vec![];
+        for item in ADMINS.range(deps.storage, start, None, Order::Ascending) {
+            if members.len() == limit {
+                break;
+            }
+            let (addr, weight) = item?;
+            if is_expired(deps, &env, &addr)? {
+                continue;
+            }
+            members.push(cw4::Member {
+                addr: addr.into_string(),
+                weight,
+            });
+        }
+
+        Ok(cw4::MemberListResponse { members })
+    }
+
+    pub fn total_weight(
+        deps: Deps,
+        env: Env,
+        at_height: Option<u64>,
+    ) -> StdResult<cw4::TotalWeightResponse> {
+        let weight = match at_height {
+            Some(height) => TOTAL_WEIGHT
+                .may_load_at_height(deps.storage, height)?
+                .unwrap_or_default(),
+            None => live_total_weight(deps, &env)?,
+        };
+
+        Ok(cw4::TotalWeightResponse { weight })
+    }
+
+    fn proposal_resp(env: &Env, id: u64, proposal: Proposal) -> ProposalResp {
+        let status = match proposal.status {
+            ProposalStatus::Open if proposal.expires.is_expired(&env.block) => {
+                ProposalStatus::Expired
+            }
+            status => status,
+        };
+
+        ProposalResp {
+            id,
+            proposer: proposal.proposer,
+            action: proposal.action,
+            status,
+            expires: proposal.expires,
+            threshold: proposal.threshold,
+            total_admins: proposal.total_admins,
+            yes: proposal.yes,
+            no: proposal.no,
+        }
+    }
+
+    pub fn proposal(deps: Deps, env: Env, proposal_id: u64) -> StdResult<ProposalResp> {
+        let proposal = PROPOSALS.load(deps.storage, proposal_id)?;
+
+        Ok(proposal_resp(&env, proposal_id, proposal))
+    }
+
+    pub fn list_proposals(
+        deps: Deps,
+        env: Env,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ProposalListResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let proposals = PROPOSALS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(id, proposal)| proposal_resp(&env, id, proposal)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(ProposalListResp { proposals })
+    }
+
+    pub fn list_votes(
+        deps: Deps,
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<VoteListResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start_after = start_after
+            .map(|addr| deps.api.addr_validate(&addr))
+            .transpose()?;
+        let start = start_after.as_ref().map(Bound::exclusive);
+
+        let votes = VOTES
+            .prefix(proposal_id)
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| item.map(|(voter, vote)| VoteResp { voter, vote }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(VoteListResp { votes })
+    }
+
+    pub fn dust(deps: Deps) -> StdResult<DustResp> {
+        let dust = DUST
+            .range(deps.storage, None, None, Order::Ascending)
+            .filter(|item| !matches!(item, Ok((_, amount)) if amount.is_zero()))
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DustResp { dust })
+    }
+
+    pub fn donation_history(
+        deps: Deps,
+        donor: Option<String>,
+        min_height: Option<u64>,
+        max_height: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<DonationHistoryResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let min_height = min_height.unwrap_or_default();
+        let max_height = max_height.unwrap_or(u64::MAX);
+        let ledger = donations();
+
+        // Ids grow with block height, so both indexes yield donations oldest first.
+        let records: Box<dyn Iterator<Item = StdResult<(u64, DonationRecord)>>> = match donor {
+            Some(donor) => {
+                let donor = deps.api.addr_validate(&donor)?;
+                let start = start_after.map(Bound::exclusive);
+                let records = ledger
+                    .idx
+                    .donor
+                    .prefix(donor)
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .filter(
+                        move |item| !matches!(item, Ok((_, record)) if record.height < min_height),
+                    );
+                Box::new(records)
+            }
+            None => {
+                let start = match start_after {
+                    Some(id) => {
+                        let height = ledger.load(deps.storage, id)?.height;
+                        if height < min_height {
+                            Bound::inclusive((min_height, 0))
+                        } else {
+                            Bound::exclusive((height, id))
+                        }
+                    }
+                    None => Bound::inclusive((min_height, 0)),
+                };
+                let records = ledger.idx.height.range(
+                    deps.storage,
+                    Some(start),
+                    Some(Bound::inclusive((max_height, u64::MAX))),
+                    Order::Ascending,
+                );
+                Box::new(records)
+            }
+        };
+
+        let donations = records
+            .take_while(|item| !matches!(item, Ok((_, record)) if record.height > max_height))
+            .take(limit)
+            .map(|item| {
+                item.map(|(id, record)| DonationResp {
+                    id,
+                    donor: record.donor,
+                    coins: record.coins,
+                    height: record.height,
+                    time: record.time,
+                    splits: record.splits,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonationHistoryResp { donations })
+    }
+
+    pub fn vesting(
+        deps: Deps,
+        env: Env,
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<VestingResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let mut vested = vec![];
+        let mut unvested = vec![];
+        let mut last_stream = None;
+
+        for item in VESTING
+            .prefix(&addr)
+            .range(
+                deps.storage,
+                start_after.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit)
+        {
+            let (id, stream) = item?;
+            last_stream = Some(id);
+            let now = stream.vested(env.block.time);
+            for ((coin, withdrawn), now) in stream.total.iter().zip(&stream.withdrawn).zip(now) {
+                exec::add_coin(&mut vested, &coin.denom, now - *withdrawn);
+                exec::add_coin(&mut unvested, &coin.denom, coin.amount - now);
+            }
+        }
+
+        Ok(VestingResp {
+            vested,
+            unvested,
+            last_stream,
+        })
+    }
+
+    pub fn hooks(deps: Deps) -> StdResult<cw4::HooksResponse> {
+        let hooks = HOOKS.query_hooks(deps)?.hooks;
+
+        Ok(cw4::HooksResponse { hooks })
+    }
+
+    pub fn has_role(deps: Deps, env: Env, addr: String, role: Role) -> StdResult<HasRoleResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        // A lapsed admin is no member even before the next execute prunes them.
+        let has_role = match role {
+            Role::Member if is_expired(deps, &env, &addr)? => false,
+            role => exec::has_role(deps, &addr, role)?,
+        };
+
+        Ok(HasRoleResp { has_role })
+    }
+
+    pub fn donation_denoms(deps: Deps) -> StdResult<DonationDenomsResp> {
+        let denoms = DONATION_DENOMS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonationDenomsResp { denoms })
+    }
+
+    pub fn cw20_tokens(deps: Deps) -> StdResult<Cw20TokensResp> {
+        let tokens = CW20_TOKENS
+            .keys(deps.storage, None, None, Order::Ascending)
+            .collect::<StdResult<_>>()?;
+
+        Ok(Cw20TokensResp { tokens })
+    }
+
+    pub fn pending_rewards(deps: Deps, addr: String) -> StdResult<PendingRewardsResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let weight = ADMINS.may_load(deps.storage, &addr)?.unwrap_or_default();
+
+        let mut rewards = vec![];
+        for item in REWARD_INDEX.range(deps.storage, None, None, Order::Ascending) {
+            let (denom, index) = item?;
+            let reward = ADMIN_REWARDS
+                .may_load(deps.storage, (&addr, &denom))?
+                .unwrap_or_default();
+
+            let pending =
+                reward.pending + (index - reward.index) * Decimal256::from_ratio(weight, 1u64);
+            let amount: Uint128 = pending.to_uint_floor().try_into()?;
+            if !amount.is_zero() {
+                rewards.push(Coin { denom, amount });
+            }
+        }
+
+        Ok(PendingRewardsResp { rewards })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::{
+        self, AdminAdded, AdminExpired, AdminRemoved, RemovalApproved, RoleGranted,
+    };
+    use crate::msg::{
+        AdminResp, AdminsListResp, DonationDenomsResp, DonationHistoryResp, DonationSplit,
+        DustResp, HasRoleResp, Member, PayoutMode, PendingRewardsResp, ProposalAction,
+        ProposalListResp, ProposalResp, ProposalStatus, ReceiveMsg, RemovalPolicy, Threshold,
+        VestingResp, VoteListResp, VoteOption, VoteResp, VotingConfig,
+    };
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{
+        coin, coins, from_binary, Addr, ContractInfoResponse, ContractResult, Decimal, Empty,
+        Event, SystemResult, Uint128, WasmQuery,
+    };
+    use cw20::{BalanceResponse, Cw20Coin, Cw20ExecuteMsg, Cw20QueryMsg};
+    use cw4::{Cw4Contract, Cw4QueryMsg, MemberDiff};
+    use cw_controllers::HookError;
+    use cw_multi_test::{App, AppResponse, ContractWrapper, Executor};
+    use cw_utils::{Duration, Expiration};
+    use std::vec;
+
+    fn member(addr: &str, weight: u64) -> Member {
+        Member {
+            addr: addr.to_owned(),
+            weight,
+            expires: None,
+        }
+    }
+
+    #[test]
+    fn donations() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(5, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(5, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("user", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            0
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance(&addr, "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            1
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin1", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            2
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin2", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            2
+        );
+    }
+
+    #[test]
+    fn donation_history() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user1"), coins(10, "eth"))
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user2"), coins(10, "eth"))
+                .unwrap();
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        for donor in ["user1", "user2", "user1"] {
+            app.execute_contract(
+                Addr::unchecked(donor),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &coins(4, "eth"),
+            )
+            .unwrap();
+            app.update_block(|block| block.height += 1);
+        }
+        let start = app.block_info().height - 3;
+
+        let history = |app: &App, msg: QueryMsg| -> Vec<(u64, String, u64)> {
+            let resp: DonationHistoryResp = app.wrap().query_wasm_smart(&addr, &msg).unwrap();
+            resp.donations
+                .into_iter()
+                .map(|donation| (donation.id, donation.donor.into_string(), donation.height))
+                .collect()
+        };
+        let query = |donor: Option<&str>, min_height, max_height, start_after, limit| {
+            QueryMsg::DonationHistory {
+                donor: donor.map(str::to_owned),
+                min_height,
+                max_height,
+                start_after,
+                limit,
+            }
+        };
+
+        let resp: DonationHistoryResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &query(None, None, None, None, Some(1)))
+            .unwrap();
+        let first = &resp.donations[0];
+        assert_eq!(first.coins, coins(4, "eth"));
+        assert_eq!(first.time, app.block_info().time);
+        assert_eq!(
+            first.splits,
+            vec![
+                DonationSplit {
+                    recipient: Addr::unchecked("admin1"),
+                    coins: coins(2, "eth"),
+                },
+                DonationSplit {
+                    recipient: Addr::unchecked("admin2"),
+                    coins: coins(2, "eth"),
+                },
+            ]
+        );
+
+        assert_eq!(
+            history(&app, query(None, None, None, None, None)),
+            vec![
+                (1, "user1".to_owned(), start),
+                (2, "user2".to_owned(), start + 1),
+                (3, "user1".to_owned(), start + 2),
+            ]
+        );
+        assert_eq!(
+            history(&app, query(None, None, None, Some(1), Some(1))),
+            vec![(2, "user2".to_owned(), start + 1)]
+        );
+        assert_eq!(
+            history(
+                &app,
+                query(None, Some(start + 1), Some(start + 1), None, None)
+            ),
+            vec![(2, "user2".to_owned(), start + 1)]
+        );
+        assert_eq!(
+            history(&app, query(Some("user1"), None, None, None, None)),
+            vec![
+                (1, "user1".to_owned(), start),
+                (3, "user1".to_owned(), start + 2),
+            ]
+        );
+        assert_eq!(
+            history(&app, query(Some("user1"), None, None, Some(1), None)),
+            vec![(3, "user1".to_owned(), start + 2)]
+        );
+        assert_eq!(
+            history(
+                &app,
+                query(Some("user1"), Some(start + 1), None, None, None)
+            ),
+            vec![(3, "user1".to_owned(), start + 2)]
+        );
+        assert_eq!(
+            history(&app, query(Some("user2"), None, Some(start), None, None)),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn weighted_donations() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(20, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 3), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(10, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin1", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            7
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin2", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            2
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdateWeights {
+                admins: vec![member("admin1", 1)],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.admins[0].share, Decimal::percent(50));
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(10, "eth"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin1", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            12
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin2", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            7
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr,
+                &ExecuteMsg::UpdateWeights {
+                    admins: vec![member("user", 1)],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn donation_remainder_rolls_over() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(10, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(5, "eth"),
+        )
+        .unwrap();
+
+        let resp: DustResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Dust {})
+            .unwrap();
+        assert_eq!(resp.dust, coins(1, "eth"));
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(5, "eth"),
+        )
+        .unwrap();
+
+        let resp: DustResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Dust {})
+            .unwrap();
+        assert_eq!(resp.dust, vec![]);
+
+        assert_eq!(
+            app.wrap()
+                .query_balance(&addr, "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            0
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin1", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            5
+        );
+        assert_eq!(
+            app.wrap()
+                .query_balance("admin2", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            5
+        );
+    }
+
+    #[test]
+    fn donate_without_admins() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(5, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr,
+                &ExecuteMsg::Donate {},
+                &coins(5, "eth"),
+            )
+            .unwrap_err();
+
+        assert_eq!(ContractError::NoAdmins {}, err.downcast().unwrap());
+        assert_eq!(
+            app.wrap()
+                .query_balance("user", "eth")
+                .unwrap()
+                .amount
+                .u128(),
+            5
+        );
+    }
+
+    #[test]
+    fn multi_denom_donations() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &Addr::unchecked("user"),
+                    vec![coin(10, "btc"), coin(10, "doge"), coin(10, "eth")],
+                )
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["btc".to_owned(), "eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &[coin(4, "btc"), coin(6, "eth")],
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            vec![coin(2, "btc"), coin(3, "eth")]
+        );
+        assert_eq!(
+            app.wrap().query_all_balances("admin2").unwrap(),
+            vec![coin(2, "btc"), coin(3, "eth")]
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &[coin(2, "btc"), coin(2, "doge")],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::UnsupportedDenom {
+                denom: "doge".to_owned()
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdateDenoms {
+                add: vec!["doge".to_owned()],
+                remove: vec!["btc".to_owned()],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: DonationDenomsResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::DonationDenoms {})
+            .unwrap();
+        assert_eq!(resp.denoms, vec!["doge".to_owned(), "eth".to_owned()]);
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr,
+            &ExecuteMsg::Donate {},
+            &coins(2, "doge"),
+        )
+        .unwrap();
+
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            vec![coin(2, "btc"), coin(1, "doge"), coin(3, "eth")]
+        );
+    }
+
+    #[test]
+    fn pull_payouts() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(24, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    payout_mode: PayoutMode::Pull,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let donate = |app: &mut App, amount| {
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &coins(amount, "eth"),
+            )
+            .unwrap();
+        };
+
+        donate(&mut app, 10);
+        assert_eq!(app.wrap().query_all_balances("admin1").unwrap(), vec![]);
+
+        let resp: PendingRewardsResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::PendingRewards {
+                    addr: "admin2".to_owned(),
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.rewards, coins(5, "eth"));
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec![member("admin3", 2)],
+            },
+            &[],
+        )
+        .unwrap();
+        donate(&mut app, 8);
+
+        app.execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::Leave {},
+            &[],
+        )
+        .unwrap();
+        donate(&mut app, 6);
+
+        for admin in ["admin1", "admin2", "admin3"] {
+            app.execute_contract(
+                Addr::unchecked(admin),
+                addr.clone(),
+                &ExecuteMsg::Claim {},
+                &[],
+            )
+            .unwrap();
+        }
+
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            coins(9, "eth")
+        );
+        assert_eq!(
+            app.wrap().query_all_balances("admin2").unwrap(),
+            coins(7, "eth")
+        );
+        assert_eq!(
+            app.wrap().query_all_balances("admin3").unwrap(),
+            coins(8, "eth")
+        );
+        assert_eq!(app.wrap().query_all_balances(&addr).unwrap(), vec![]);
+
+        let err = app
+            .execute_contract(Addr::unchecked("admin1"), addr, &ExecuteMsg::Claim {}, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NothingToClaim {
+                addr: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn vesting_payouts() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(1500, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let err = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1)],
+                    payout_mode: PayoutMode::Vesting {
+                        cliff: 200,
+                        duration: 100,
+                    },
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::InvalidVesting {}, err.downcast().unwrap());
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    payout_mode: PayoutMode::Vesting {
+                        cliff: 100,
+                        duration: 1000,
+                    },
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("user"),
+            addr.clone(),
+            &ExecuteMsg::Donate {},
+            &coins(1000, "eth"),
+        )
+        .unwrap();
+
+        let vesting = |app: &App| -> VestingResp {
+            app.wrap()
+                .query_wasm_smart(
+                    &addr,
+                    &QueryMsg::Vesting {
+                        addr: "admin1".to_owned(),
+                        start_after: None,
+                        limit: None,
+                    },
+                )
+                .unwrap()
+        };
+        let withdraw = |app: &mut App| {
+            app.execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::WithdrawVested {
+                    start_after: None,
+                    limit: None,
+                },
+                &[],
+            )
+        };
+
+        assert_eq!(
+            vesting(&app),
+            VestingResp {
+                vested: vec![],
+                unvested: coins(500, "eth"),
+                last_stream: Some(1),
+            }
+        );
+
+        // Nothing is released before the cliff.
+        app.update_block(|block| block.time = block.time.plus_seconds(50));
+        let err = withdraw(&mut app).unwrap_err();
+        assert_eq!(
+            ContractError::NothingToClaim {
+                addr: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(200));
+        assert_eq!(
+            vesting(&app),
+            VestingResp {
+                vested: coins(125, "eth"),
+                unvested: coins(375, "eth"),
+                last_stream: Some(1),
+            }
+        );
+        withdraw(&mut app).unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            coins(125, "eth")
+        );
+        assert_eq!(
+            vesting(&app),
+            VestingResp {
+                vested: vec![],
+                unvested: coins(375, "eth"),
+                last_stream: Some(1),
+            }
+        );
+
+        app.update_block(|block| block.time = block.time.plus_seconds(1000));
+        withdraw(&mut app).unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            coins(500, "eth")
+        );
+        assert_eq!(
+            vesting(&app),
+            VestingResp {
+                vested: vec![],
+                unvested: vec![],
+                last_stream: None,
+            }
+        );
+        assert_eq!(app.wrap().query_all_balances("admin2").unwrap(), vec![]);
+
+        // Streams are walked a page at a time, however many donations piled up.
+        for _ in 0..2 {
+            app.execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &coins(250, "eth"),
+            )
+            .unwrap();
+        }
+        app.update_block(|block| block.time = block.time.plus_seconds(1000));
+        let resp: VestingResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::Vesting {
+                    addr: "admin1".to_owned(),
+                    start_after: Some(2),
+                    limit: Some(1),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp,
+            VestingResp {
+                vested: coins(125, "eth"),
+                unvested: vec![],
+                last_stream: Some(3),
+            }
+        );
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::WithdrawVested {
+                    start_after: None,
+                    limit: Some(1),
+                },
+                &[],
+            )
+            .unwrap();
+        assert!(resp.has_event(&Event::new("wasm").add_attribute("last_stream", "2")));
+        assert_eq!(
+            app.wrap().query_all_balances("admin1").unwrap(),
+            coins(625, "eth")
+        );
+    }
+
+    #[test]
+    fn cw20_donations() {
+        let mut app = App::default();
+
+        let token_code = ContractWrapper::new(
+            cw20_base::contract::execute,
+            cw20_base::contract::instantiate,
+            cw20_base::contract::query,
+        );
+        let token_code_id = app.store_code(Box::new(token_code));
+
+        let token_msg = |symbol: &str| cw20_base::msg::InstantiateMsg {
+            name: format!("{symbol} token"),
+            symbol: symbol.to_owned(),
+            decimals: 6,
+            initial_balances: vec![Cw20Coin {
+                address: "user".to_owned(),
+                amount: Uint128::new(10),
+            }],
+            mint: None,
+            marketing: None,
+        };
+        let token = app
+            .instantiate_contract(
+                token_code_id,
+                Addr::unchecked("owner"),
+                &token_msg("TKN"),
+                &[],
+                "Token",
+                None,
+            )
+            .unwrap();
+        let other_token = app
+            .instantiate_contract(
+                token_code_id,
+                Addr::unchecked("owner"),
+                &token_msg("OTH"),
+                &[],
+                "Other token",
+                None,
+            )
+            .unwrap();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    cw20_tokens: vec![token.to_string()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let send = Cw20ExecuteMsg::Send {
+            contract: addr.to_string(),
+            amount: Uint128::new(5),
+            msg: to_binary(&ReceiveMsg::Donate {}).unwrap(),
+        };
+
+        app.execute_contract(Addr::unchecked("user"), token.clone(), &send, &[])
+            .unwrap();
+
+        let balance = |app: &App, token: &Addr, addr: &str| {
+            let resp: BalanceResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    token,
+                    &Cw20QueryMsg::Balance {
+                        address: addr.to_owned(),
+                    },
+                )
+                .unwrap();
+            resp.balance.u128()
+        };
+
+        assert_eq!(balance(&app, &token, "admin1"), 2);
+        assert_eq!(balance(&app, &token, "admin2"), 2);
+        assert_eq!(balance(&app, &token, addr.as_str()), 1);
+
+        let resp: DustResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Dust {})
+            .unwrap();
+        assert_eq!(resp.dust, coins(1, format!("cw20:{token}")));
+
+        let err = app
+            .execute_contract(Addr::unchecked("user"), other_token.clone(), &send, &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::UnsupportedToken { token: other_token },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn add_members() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("owner", 1)],
+                    donation_denoms: vec!["atom".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::Propose {
+                    action: ProposalAction::AddMembers {
+                        admins: vec![member("user", 1)],
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::VotingDisabled {}, err.downcast().unwrap());
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr,
+                &ExecuteMsg::AddMembers {
+                    admins: vec![member("user", 1)],
+                },
+                &[],
+            )
+            .unwrap();
+
+        assert!(resp.has_event(
+            &Event::new("wasm")
+                .add_attribute("action", "add_members")
+                .add_attribute("added_count", "1")
+        ));
+        assert_eq!(
+            events::parse::<AdminAdded>(&resp.events),
+            vec![AdminAdded {
+                addr: Addr::unchecked("user")
+            }]
+        );
+    }
+
+    fn removal_app(removal_policy: RemovalPolicy) -> (App, Addr) {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        member("admin2", 1),
+                        member("admin3", 1),
+                    ],
+                    removal_policy,
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        (app, addr)
+    }
+
+    fn admin_addrs(app: &App, addr: &Addr) -> Vec<String> {
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        resp.admins
+            .into_iter()
+            .map(|admin| admin.addr.into_string())
+            .collect()
+    }
+
+    fn remove(app: &mut App, addr: &Addr, sender: &str, target: &str) -> AppResponse {
+        app.execute_contract(
+            Addr::unchecked(sender),
+            addr.clone(),
+            &ExecuteMsg::RemoveMembers {
+                admins: vec![target.to_owned()],
+            },
+            &[],
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn remove_members() {
+        let (mut app, addr) = removal_app(RemovalPolicy::AnyAdmin);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned(), "user".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotAdmin {
+                addr: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp = remove(&mut app, &addr, "admin1", "admin2");
+        assert_eq!(
+            events::parse::<AdminRemoved>(&resp.events),
+            vec![AdminRemoved {
+                addr: Addr::unchecked("admin2")
+            }]
+        );
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin3"]);
+    }
+
+    #[test]
+    fn remove_members_by_majority() {
+        let (mut app, addr) = removal_app(RemovalPolicy::Majority);
+
+        let resp = remove(&mut app, &addr, "admin1", "admin3");
+        assert_eq!(
+            events::parse::<RemovalApproved>(&resp.events),
+            vec![RemovalApproved {
+                addr: Addr::unchecked("admin3"),
+                approver: Addr::unchecked("admin1"),
+                approvals: 1,
+                required: 2,
+            }]
+        );
+        // Asking twice does not count twice.
+        remove(&mut app, &addr, "admin1", "admin3");
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2", "admin3"]);
+
+        let resp = remove(&mut app, &addr, "admin2", "admin3");
+        assert_eq!(
+            events::parse::<AdminRemoved>(&resp.events),
+            vec![AdminRemoved {
+                addr: Addr::unchecked("admin3")
+            }]
+        );
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2"]);
+    }
+
+    #[test]
+    fn remove_members_by_all_but_target() {
+        let (mut app, addr) = removal_app(RemovalPolicy::AllButTarget);
+
+        // The target's own approval does not count.
+        remove(&mut app, &addr, "admin3", "admin3");
+        remove(&mut app, &addr, "admin1", "admin3");
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2", "admin3"]);
+
+        remove(&mut app, &addr, "admin2", "admin3");
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2"]);
+    }
+
+    #[test]
+    fn membership_expiry() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(10, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let height = app.block_info().height;
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        Member {
+                            expires: Some(Expiration::AtHeight(height + 5)),
+                            ..member("admin2", 1)
+                        },
+                    ],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::RenewMembership {
+                    addr: "admin2".to_owned(),
+                    expires: Some(Expiration::AtHeight(height)),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InvalidExpiration {
+                expires: Expiration::AtHeight(height)
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::RenewMembership {
+                addr: "admin2".to_owned(),
+                expires: Some(Expiration::AtHeight(height + 10)),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| block.height += 7);
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.admins[1].expires,
+            Some(Expiration::AtHeight(height + 10))
+        );
+
+        app.update_block(|block| block.height += 3);
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![AdminResp {
+                    addr: Addr::unchecked("admin1"),
+                    weight: 1,
+                    share: Decimal::one(),
+                    expires: None,
+                }],
+            }
+        );
+        let resp: HasRoleResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::HasRole {
+                    addr: "admin2".to_owned(),
+                    role: Role::Member,
+                },
+            )
+            .unwrap();
+        assert!(!resp.has_role);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec![member("user", 1)],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("admin2")
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &coins(10, "eth"),
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<AdminExpired>(&resp.events),
+            vec![AdminExpired {
+                addr: Addr::unchecked("admin2")
+            }]
+        );
+        assert_eq!(
+            app.wrap().query_balance("admin1", "eth").unwrap().amount,
+            Uint128::new(10)
+        );
+        assert_eq!(
+            app.wrap().query_balance("admin2", "eth").unwrap().amount,
+            Uint128::zero()
+        );
+    }
+
+    #[test]
+    fn membership_expiry_by_time() {
+        let mut app = App::new(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &Addr::unchecked("user"), coins(10, "eth"))
+                .unwrap()
+        });
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let block = app.block_info();
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        Member {
+                            expires: Some(Expiration::AtTime(block.time.plus_seconds(100))),
+                            ..member("admin2", 2)
+                        },
+                        Member {
+                            expires: Some(Expiration::AtTime(block.time.plus_seconds(100))),
+                            ..member("admin3", 3)
+                        },
+                    ],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        // Moving admin3 to a height expiry must drop it from the time queue.
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::RenewMembership {
+                addr: "admin3".to_owned(),
+                expires: Some(Expiration::AtHeight(block.height + 100)),
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| {
+            block.height += 1;
+            block.time = block.time.plus_seconds(100);
+        });
+        let resp: cw4::TotalWeightResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &Cw4QueryMsg::TotalWeight { at_height: None })
+            .unwrap();
+        assert_eq!(resp.weight, 4);
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr.clone(),
+                &ExecuteMsg::Donate {},
+                &coins(10, "eth"),
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<AdminExpired>(&resp.events),
+            vec![AdminExpired {
+                addr: Addr::unchecked("admin2")
+            }]
+        );
+
+        let resp: cw4::TotalWeightResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &Cw4QueryMsg::TotalWeight { at_height: None })
+            .unwrap();
+        assert_eq!(resp.weight, 4);
+    }
+
+    #[test]
+    fn min_admins() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        member("admin2", 1),
+                        member("admin3", 1),
+                    ],
+                    min_admins: Some(2),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            Addr::unchecked("admin3"),
+            addr.clone(),
+            &ExecuteMsg::Leave {},
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::Leave {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TooFewAdmins { min: 2 },
+            err.downcast().unwrap()
+        );
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::RemoveMembers {
+                    admins: vec!["admin2".to_owned()],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TooFewAdmins { min: 2 },
+            err.downcast().unwrap()
+        );
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2"]);
+    }
+
+    #[test]
+    fn min_admins_with_voting() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        member("admin2", 1),
+                        member("admin3", 1),
+                    ],
+                    voting: Some(VotingConfig {
+                        threshold: Threshold::AbsoluteCount { count: 1 },
+                        max_voting_period: Duration::Height(10),
+                    }),
+                    min_admins: Some(2),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        // The proposer's own vote meets the threshold, so the removal runs at once.
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::Propose {
+                    action: ProposalAction::RemoveMembers {
+                        admins: vec!["admin1".to_owned(), "admin2".to_owned()],
+                    },
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TooFewAdmins { min: 2 },
+            err.downcast().unwrap()
+        );
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2", "admin3"]);
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                action: ProposalAction::RemoveMembers {
+                    admins: vec!["admin2".to_owned()],
+                },
+            },
+            &[],
+        )
+        .unwrap();
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin3"]);
+    }
+
+    #[test]
+    fn reseed_empty_group() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let height = app.block_info().height;
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![Member {
+                        expires: Some(Expiration::AtHeight(height + 1)),
+                        ..member("admin1", 1)
+                    }],
+                    guardian: Some("guardian".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                Some("wasm_admin".to_owned()),
+            )
+            .unwrap();
+
+        let reseed = |app: &mut App, sender: &str, admin: &str| {
+            app.execute_contract(
+                Addr::unchecked(sender),
+                addr.clone(),
+                &ExecuteMsg::Reseed {
+                    admins: vec![member(admin, 1)],
+                },
+                &[],
+            )
+        };
+
+        let err = reseed(&mut app, "guardian", "admin2").unwrap_err();
+        assert_eq!(ContractError::GroupNotEmpty {}, err.downcast().unwrap());
+
+        app.update_block(|block| block.height += 1);
+        assert!(admin_addrs(&app, &addr).is_empty());
+
+        let err = reseed(&mut app, "user", "admin2").unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
+        );
+
+        reseed(&mut app, "guardian", "admin2").unwrap();
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin2"]);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::Leave {},
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::Leave {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::TooFewAdmins { min: 1 },
+            err.downcast().unwrap()
+        );
+
+        let err = reseed(&mut app, "wasm_admin", "admin3").unwrap_err();
+        assert_eq!(ContractError::GroupNotEmpty {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn duplicate_admins() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let err = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin1", 2)],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::DuplicateAdmin {
+                addr: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1)],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let add = |app: &mut App, admins: &[&str]| {
+            app.execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: admins.iter().map(|admin| member(admin, 1)).collect(),
+                },
+                &[],
+            )
+        };
+
+        let err = add(&mut app, &["admin2", "admin1"]).unwrap_err();
+        assert_eq!(
+            ContractError::AlreadyAdmin {
+                addr: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        let err = add(&mut app, &["admin2", "admin2"]).unwrap_err();
+        assert_eq!(
+            ContractError::DuplicateAdmin {
+                addr: Addr::unchecked("admin2")
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp = add(&mut app, &["admin2", "admin3"]).unwrap();
+        assert!(resp.has_event(&Event::new("wasm").add_attribute("added_count", "2")));
+        assert_eq!(admin_addrs(&app, &addr), vec!["admin1", "admin2", "admin3"]);
+    }
+
+    #[test]
+    fn roles() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("creator"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1)],
+                    owner: Some("owner".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let has_role = |app: &App, holder: &str, role: Role| -> bool {
+            let resp: HasRoleResp = app
+                .wrap()
+                .query_wasm_smart(
+                    &addr,
+                    &QueryMsg::HasRole {
+                        addr: holder.to_owned(),
+                        role,
+                    },
+                )
+                .unwrap();
+            resp.has_role
+        };
+        let execute = |app: &mut App, sender: &str, msg: ExecuteMsg| {
+            app.execute_contract(Addr::unchecked(sender), addr.clone(), &msg, &[])
+        };
+        let add_member = |admin: &str| ExecuteMsg::AddMembers {
+            admins: vec![member(admin, 1)],
+        };
+
+        assert!(has_role(&app, "owner", Role::Owner));
+        assert!(!has_role(&app, "creator", Role::Owner));
+        assert!(has_role(&app, "admin1", Role::Member));
+
+        // Being a member no longer allows managing the membership.
+        let err = execute(&mut app, "admin1", add_member("admin2")).unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        let grant = |role| ExecuteMsg::GrantRole {
+            addr: "manager".to_owned(),
+            role,
+        };
+        let err = execute(&mut app, "admin1", grant(Role::MembershipManager)).unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+        let err = execute(&mut app, "owner", grant(Role::Member)).unwrap_err();
+        assert_eq!(
+            ContractError::DerivedRole { role: Role::Member },
+            err.downcast().unwrap()
+        );
+
+        let resp = execute(&mut app, "owner", grant(Role::MembershipManager)).unwrap();
+        assert_eq!(
+            events::parse::<RoleGranted>(&resp.events),
+            vec![RoleGranted {
+                addr: Addr::unchecked("manager"),
+                role: Role::MembershipManager,
+            }]
+        );
+        assert!(has_role(&app, "manager", Role::MembershipManager));
+        execute(&mut app, "manager", add_member("admin2")).unwrap();
+        assert!(has_role(&app, "admin2", Role::Member));
+        assert!(!has_role(&app, "manager", Role::Member));
+
+        let err = execute(
+            &mut app,
+            "manager",
+            ExecuteMsg::UpdateDenoms {
+                add: vec!["eth".to_owned()],
+                remove: vec![],
+            },
+        )
+        .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("manager")
+            },
+            err.downcast().unwrap()
+        );
+
+        execute(
+            &mut app,
+            "owner",
+            ExecuteMsg::RevokeRole {
+                addr: "manager".to_owned(),
+                role: Role::MembershipManager,
+            },
+        )
+        .unwrap();
+        execute(&mut app, "manager", add_member("admin3")).unwrap_err();
+
+        let err = execute(
+            &mut app,
+            "owner",
+            ExecuteMsg::RevokeRole {
+                addr: "owner".to_owned(),
+                role: Role::Owner,
+            },
+        )
+        .unwrap_err();
+        assert_eq!(ContractError::LastOwner {}, err.downcast().unwrap());
+    }
+
+    mod hook {
+        use cosmwasm_schema::cw_serde;
+        use cosmwasm_std::{
+            to_binary, Binary, Deps, DepsMut, Empty, Env, MessageInfo, Response, StdResult,
+        };
+        use cw4::{MemberChangedHookMsg, MemberDiff};
+        use cw_storage_plus::Item;
+
+        #[cw_serde]
+        pub enum ExecMsg {
+            MemberChangedHook(MemberChangedHookMsg),
+        }
+
+        /// Every diff the hook was notified about, in order.
+        const DIFFS: Item<Vec<MemberDiff>> = Item::new("diffs");
+
+        pub fn instantiate(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            _msg: Empty,
+        ) -> StdResult<Response> {
+            DIFFS.save(deps.storage, &vec![])?;
+            Ok(Response::new())
+        }
+
+        pub fn execute(
+            deps: DepsMut,
+            _env: Env,
+            _info: MessageInfo,
+            msg: ExecMsg,
+        ) -> StdResult<Response> {
+            let ExecMsg::MemberChangedHook(msg) = msg;
+            DIFFS.update(deps.storage, |mut diffs| -> StdResult<_> {
+                diffs.extend(msg.diffs);
+                Ok(diffs)
+            })?;
+            Ok(Response::new())
+        }
+
+        pub fn query(deps: Deps, _env: Env, _msg: Empty) -> StdResult<Binary> {
+            to_binary(&DIFFS.load(deps.storage)?)
+        }
+    }
+
+    #[test]
+    fn membership_hooks() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+        let hook_code = ContractWrapper::new(hook::execute, hook::instantiate, hook::query);
+        let hook_code_id = app.store_code(Box::new(hook_code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("creator"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1)],
+                    owner: Some("owner".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+        let hook_addr = app
+            .instantiate_contract(
+                hook_code_id,
+                Addr::unchecked("creator"),
+                &Empty {},
+                &[],
+                "Hook",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::AddHook {
+                    addr: hook_addr.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddHook {
+                addr: hook_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::AddHook {
+                    addr: hook_addr.to_string(),
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Hook(HookError::HookAlreadyRegistered {}),
+            err.downcast().unwrap()
+        );
+
+        let resp: cw4::HooksResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Hooks {})
+            .unwrap();
+        assert_eq!(resp.hooks, vec![hook_addr.to_string()]);
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec![member("admin2", 2)],
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::UpdateWeights {
+                admins: vec![member("admin1", 3)],
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("admin2"),
+            addr.clone(),
+            &ExecuteMsg::Leave {},
+            &[],
+        )
+        .unwrap();
+
+        let diffs: Vec<MemberDiff> = app.wrap().query_wasm_smart(&hook_addr, &Empty {}).unwrap();
+        assert_eq!(
+            diffs,
+            vec![
+                MemberDiff::new("admin2", None, Some(2)),
+                MemberDiff::new("admin1", Some(1), Some(3)),
+                MemberDiff::new("admin2", Some(2), None),
+            ]
+        );
+
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::RemoveHook {
+                addr: hook_addr.to_string(),
+            },
+            &[],
+        )
+        .unwrap();
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec![member("admin3", 1)],
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: cw4::HooksResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Hooks {})
+            .unwrap();
+        assert!(resp.hooks.is_empty());
+
+        let diffs: Vec<MemberDiff> = app.wrap().query_wasm_smart(&hook_addr, &Empty {}).unwrap();
+        assert_eq!(diffs.len(), 3);
+    }
+
+    #[test]
+    fn cw4_queries() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("creator"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 2)],
+                    owner: Some("owner".to_owned()),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| block.height += 1);
+        let height = app.block_info().height;
+        app.execute_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &ExecuteMsg::AddMembers {
+                admins: vec![member("admin3", 3)],
+            },
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.height += 1);
+
+        // Queried through the cw4 message itself, as a cw4 consumer would.
+        let resp: cw4::AdminResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &Cw4QueryMsg::Admin {})
+            .unwrap();
+        assert_eq!(resp.admin, Some("owner".to_owned()));
+
+        let total_weight = |app: &App, at_height| -> u64 {
+            let resp: cw4::TotalWeightResponse = app
+                .wrap()
+                .query_wasm_smart(&addr, &Cw4QueryMsg::TotalWeight { at_height })
+                .unwrap();
+            resp.weight
+        };
+        assert_eq!(total_weight(&app, None), 6);
+        assert_eq!(total_weight(&app, Some(height)), 3);
+        assert_eq!(total_weight(&app, Some(height + 1)), 6);
+
+        let weight = |app: &App, addr_: &str, at_height| -> Option<u64> {
+            let resp: cw4::MemberResponse = app
+                .wrap()
+                .query_wasm_smart(
+                    &addr,
+                    &Cw4QueryMsg::Member {
+                        addr: addr_.to_owned(),
+                        at_height,
+                    },
+                )
+                .unwrap();
+            resp.weight
+        };
+        assert_eq!(weight(&app, "admin3", None), Some(3));
+        assert_eq!(weight(&app, "admin3", Some(height)), None);
+        assert_eq!(weight(&app, "admin2", Some(height)), Some(2));
+        assert_eq!(weight(&app, "stranger", None), None);
+
+        // cw4 helpers, as used by cw3-flex-multisig, read the storage raw.
+        let group = Cw4Contract::new(addr.clone());
+        assert_eq!(group.total_weight(&app.wrap()).unwrap(), 6);
+        assert_eq!(
+            group
+                .is_member(&app.wrap(), &Addr::unchecked("admin3"), None)
+                .unwrap(),
+            Some(3)
+        );
+        assert_eq!(
+            group
+                .is_member(&app.wrap(), &Addr::unchecked("stranger"), None)
+                .unwrap(),
+            None
+        );
+
+        let resp: cw4::MemberListResponse = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &Cw4QueryMsg::ListMembers {
+                    start_after: Some("admin1".to_owned()),
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.members,
+            vec![
+                cw4::Member {
+                    addr: "admin2".to_owned(),
+                    weight: 2
+                },
+                cw4::Member {
+                    addr: "admin3".to_owned(),
+                    weight: 3
+                },
+            ]
+        );
+
+        let resp: cw4::HooksResponse = app
+            .wrap()
+            .query_wasm_smart(&addr, &Cw4QueryMsg::Hooks {})
+            .unwrap();
+        assert!(resp.hooks.is_empty());
+    }
+
+    #[test]
+    fn unauthorized() {
+        let mut app = App::default();
+
+        // the code is about my contract and we don't know the size of code so we should store the code into heep with using Box
+        // Box is needed to store the code.
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr,
+                &ExecuteMsg::AddMembers {
+                    admins: vec![member("user", 1)],
+                },
+                &[],
+            )
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::Unauthorized {
+                sender: Addr::unchecked("user")
+            },
+            // Hopefully, as I said before,
+            // anyhow errors can recover their original type using the downcast function.
+            err.downcast().unwrap()
+        )
+    }
+
+    #[test]
+    fn contract_instantiate() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        assert_eq!(resp, AdminsListResp { admins: vec![] });
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract 2",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+
+        dbg!(resp.clone());
+
+        assert_eq!(
+            resp,
+            AdminsListResp {
+                admins: vec![
+                    AdminResp {
+                        addr: Addr::unchecked("admin1"),
+                        weight: 1,
+                        share: Decimal::percent(50),
+                        expires: None,
+                    },
+                    AdminResp {
+                        addr: Addr::unchecked("admin2"),
+                        weight: 1,
+                        share: Decimal::percent(50),
+                        expires: None,
+                    },
+                ],
+            }
         );
+    }
+
+    #[test]
+    fn admins_list_pagination() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        member("admin2", 1),
+                        member("admin3", 1),
+                    ],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: Some(2),
+                },
+            )
+            .unwrap();
+
+        let admins: Vec<_> = resp.admins.into_iter().map(|admin| admin.addr).collect();
         assert_eq!(
-            app.wrap()
-                .query_balance(&addr, "eth")
-                .unwrap()
-                .amount
-                .u128(),
-            1
+            admins,
+            vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")]
+        );
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: Some("admin2".to_owned()),
+                    limit: Some(2),
+                },
+            )
+            .unwrap();
+
+        let admins: Vec<_> = resp.admins.into_iter().map(|admin| admin.addr).collect();
+        assert_eq!(admins, vec![Addr::unchecked("admin3")]);
+    }
+
+    #[test]
+    fn greet_query_with_appchain() {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp: GreetResp = app
+            .wrap()
+            .query_wasm_smart(addr, &QueryMsg::Greet {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            GreetResp {
+                message: "Hello World".to_owned()
+            }
         );
+    }
+
+    #[test]
+    fn greet_query_with_mock() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+
+        instantiate(
+            deps.as_mut(),
+            env.clone(),
+            mock_info("sender", &[]),
+            InstantiateMsg {
+                admins: vec![],
+                donation_denoms: vec!["eth".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+
+        let resp = query(deps.as_ref(), env, QueryMsg::Greet {}).unwrap();
+        let resp: GreetResp = from_binary(&resp).unwrap();
+
         assert_eq!(
-            app.wrap()
-                .query_balance("admin1", "eth")
-                .unwrap()
-                .amount
-                .u128(),
-            2
+            resp,
+            GreetResp {
+                message: "Hello World".to_owned()
+            }
+        );
+    }
+
+    fn voting_app(threshold: Threshold, max_voting_period: Duration) -> (App, Addr) {
+        let mut app = App::default();
+
+        let code = ContractWrapper::new(execute, instantiate, query);
+        let code_id = app.store_code(Box::new(code));
+
+        let addr = app
+            .instantiate_contract(
+                code_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    admins: vec![
+                        member("admin1", 1),
+                        member("admin2", 1),
+                        member("admin3", 1),
+                    ],
+                    donation_denoms: vec!["eth".to_owned()],
+                    voting: Some(VotingConfig {
+                        threshold,
+                        max_voting_period,
+                    }),
+                    ..Default::default()
+                },
+                &[],
+                "Contract",
+                None,
+            )
+            .unwrap();
+
+        app.update_block(|block| block.height += 1);
+
+        (app, addr)
+    }
+
+    #[test]
+    fn proposal_executes_after_threshold() {
+        let (mut app, addr) =
+            voting_app(Threshold::AbsoluteCount { count: 2 }, Duration::Height(10));
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("owner"),
+                addr.clone(),
+                &ExecuteMsg::AddMembers {
+                    admins: vec![member("user", 1)],
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::ProposalRequired {}, err.downcast().unwrap());
+
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                action: ProposalAction::AddMembers {
+                    admins: vec![member("user", 1)],
+                },
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: ProposalResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        assert_eq!(resp.status, ProposalStatus::Open);
+        assert_eq!(resp.yes, 1);
+        assert_eq!(resp.total_admins, 3);
+
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin1"),
+                addr.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
+                },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::AlreadyVoted {
+                id: 1,
+                voter: Addr::unchecked("admin1")
+            },
+            err.downcast().unwrap()
         );
+
+        let resp = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr.clone(),
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
+                },
+                &[],
+            )
+            .unwrap();
+
+        assert_eq!(events::parse::<AdminAdded>(&resp.events).len(), 1);
+
+        let resp: ProposalResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Proposal { proposal_id: 1 })
+            .unwrap();
+        assert_eq!(resp.status, ProposalStatus::Executed);
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert!(resp.admins.iter().any(|admin| admin.addr == "user"));
+
+        // Admins who joined after a proposal was created cannot vote on it.
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                action: ProposalAction::RemoveMembers {
+                    admins: vec!["admin3".to_owned()],
+                },
+            },
+            &[],
+        )
+        .unwrap();
+        let err = app
+            .execute_contract(
+                Addr::unchecked("user"),
+                addr,
+                &ExecuteMsg::Vote {
+                    proposal_id: 2,
+                    vote: VoteOption::Yes,
+                },
+                &[],
+            )
+            .unwrap_err();
         assert_eq!(
-            app.wrap()
-                .query_balance("admin2", "eth")
-                .unwrap()
-                .amount
-                .u128(),
-            2
+            ContractError::NotEligible {
+                id: 2,
+                voter: Addr::unchecked("user")
+            },
+            err.downcast().unwrap()
         );
     }
 
     #[test]
-    fn add_members() {
-        let mut app = App::default();
+    fn proposal_rejected_by_votes() {
+        let (mut app, addr) = voting_app(
+            Threshold::Percentage {
+                percentage: Decimal::percent(75),
+            },
+            Duration::Height(10),
+        );
 
-        let code = ContractWrapper::new(execute, instantiate, query);
-        let code_id = app.store_code(Box::new(code));
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                action: ProposalAction::RemoveMembers {
+                    admins: vec!["admin3".to_owned()],
+                },
+            },
+            &[],
+        )
+        .unwrap();
 
-        let addr = app
-            .instantiate_contract(
-                code_id,
-                Addr::unchecked("owner"),
-                &InstantiateMsg {
-                    admins: vec!["owner".to_owned()],
-                    donation_denom: "atom".to_string(),
+        app.execute_contract(
+            Addr::unchecked("admin3"),
+            addr.clone(),
+            &ExecuteMsg::Vote {
+                proposal_id: 1,
+                vote: VoteOption::No,
+            },
+            &[],
+        )
+        .unwrap();
+
+        let resp: ProposalListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::ListProposals {
+                    start_after: None,
+                    limit: None,
                 },
-                &[],
-                "Contract",
-                None,
             )
             .unwrap();
+        assert_eq!(resp.proposals.len(), 1);
+        assert_eq!(resp.proposals[0].status, ProposalStatus::Rejected);
 
-        let resp = app
-            .execute_contract(
-                Addr::unchecked("owner"),
-                addr,
-                &ExecuteMsg::AddMembers {
-                    admins: vec!["user".to_owned()],
+        let resp: VoteListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::ListVotes {
+                    proposal_id: 1,
+                    start_after: None,
+                    limit: None,
                 },
-                &[],
             )
             .unwrap();
-
-        let wasm = resp.events.iter().find(|event| event.ty == "wasm").unwrap();
-        assert_eq!(
-            wasm.attributes
-                .iter()
-                .find(|attr| attr.key == "action")
-                .unwrap()
-                .value,
-            "add_members"
-        );
         assert_eq!(
-            wasm.attributes
-                .iter()
-                .find(|attr| attr.key == "added_count")
-                .unwrap()
-                .value,
-            "1"
+            resp.votes,
+            vec![
+                VoteResp {
+                    voter: Addr::unchecked("admin1"),
+                    vote: VoteOption::Yes,
+                },
+                VoteResp {
+                    voter: Addr::unchecked("admin3"),
+                    vote: VoteOption::No,
+                },
+            ]
         );
 
-        let admin_added: Vec<_> = resp
-            .events
-            .iter()
-            .filter(|event| event.ty == "wasm-admin_added")
-            .collect();
-        assert_eq!(admin_added.len(), 1);
+        let err = app
+            .execute_contract(
+                Addr::unchecked("admin2"),
+                addr,
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
+                },
+                &[],
+            )
+            .unwrap_err();
         assert_eq!(
-            admin_added[0]
-                .attributes
-                .iter()
-                .find(|attr| attr.key == "addr")
-                .unwrap()
-                .value,
-            "user"
-        )
+            ContractError::ProposalNotOpen { id: 1 },
+            err.downcast().unwrap()
+        );
     }
 
     #[test]
-    fn unauthorized() {
-        let mut app = App::default();
-
-        // the code is about my contract and we don't know the size of code so we should store the code into heep with using Box
-        // Box is needed to store the code.
-        let code = ContractWrapper::new(execute, instantiate, query);
-        let code_id = app.store_code(Box::new(code));
+    fn proposal_expires() {
+        let (mut app, addr) =
+            voting_app(Threshold::AbsoluteCount { count: 2 }, Duration::Height(5));
 
-        let addr = app
-            .instantiate_contract(
-                code_id,
-                Addr::unchecked("owner"),
-                &InstantiateMsg {
-                    admins: vec![],
-                    donation_denom: "eth".to_owned(),
+        app.execute_contract(
+            Addr::unchecked("admin1"),
+            addr.clone(),
+            &ExecuteMsg::Propose {
+                action: ProposalAction::AddMembers {
+                    admins: vec![member("user", 1)],
                 },
-                &[],
-                "Contract",
-                None,
-            )
+            },
+            &[],
+        )
+        .unwrap();
+
+        app.update_block(|block| block.height += 5);
+
+        let resp: ProposalResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::Proposal { proposal_id: 1 })
             .unwrap();
+        assert_eq!(resp.status, ProposalStatus::Expired);
 
         let err = app
             .execute_contract(
-                Addr::unchecked("user"),
+                Addr::unchecked("admin2"),
                 addr,
-                &ExecuteMsg::AddMembers {
-                    admins: vec!["user".to_owned()],
+                &ExecuteMsg::Vote {
+                    proposal_id: 1,
+                    vote: VoteOption::Yes,
                 },
                 &[],
             )
             .unwrap_err();
-
         assert_eq!(
-            ContractError::Unauthorized {
-                sender: Addr::unchecked("user")
-            },
-            // Hopefully, as I said before,
-            // anyhow errors can recover their original type using the downcast function.
+            ContractError::ProposalExpired { id: 1 },
             err.downcast().unwrap()
-        )
+        );
     }
 
     #[test]
-    fn contract_instantiate() {
+    fn migrate_keeps_version() {
         let mut app = App::default();
 
-        let code = ContractWrapper::new(execute, instantiate, query);
+        let code = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
         let code_id = app.store_code(Box::new(code));
 
         let addr = app
             .instantiate_contract(
                 code_id,
                 Addr::unchecked("owner"),
-                &InstantiateMsg {
-                    admins: vec![],
-                    donation_denom: "eth".to_owned(),
-                },
+                &InstantiateMsg::default(),
                 &[],
                 "Contract",
-                None,
+                Some("owner".to_owned()),
             )
             .unwrap();
 
-        let resp: AdminsListResp = app
+        let code = ContractWrapper::new(execute, instantiate, query).with_migrate(migrate);
+        let new_code_id = app.store_code(Box::new(code));
+
+        app.migrate_contract(
+            Addr::unchecked("owner"),
+            addr.clone(),
+            &MigrateMsg {},
+            new_code_id,
+        )
+        .unwrap();
+
+        let resp: cw2::ContractVersion = app
             .wrap()
-            .query_wasm_smart(addr, &QueryMsg::AdminsList {})
+            .query_wasm_smart(addr, &QueryMsg::ContractVersion {})
             .unwrap();
+        assert_eq!(
+            resp,
+            cw2::ContractVersion {
+                contract: CONTRACT_NAME.to_owned(),
+                version: CONTRACT_VERSION.to_owned(),
+            }
+        );
+    }
 
-        assert_eq!(resp, AdminsListResp { admins: vec![] });
+    #[test]
+    fn migrate_rejects_downgrade_and_foreign_contract() {
+        let mut deps = mock_dependencies();
 
-        let addr = app
-            .instantiate_contract(
-                code_id,
-                Addr::unchecked("owner"),
-                &InstantiateMsg {
-                    admins: vec!["admin1".to_owned(), "admin2".to_owned()],
-                    donation_denom: "eth".to_owned(),
+        cw2::set_contract_version(deps.as_mut().storage, CONTRACT_NAME, "99.0.0").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Downgrade {
+                stored: "99.0.0".to_owned(),
+                current: CONTRACT_VERSION.to_owned(),
+            }
+        );
+
+        cw2::set_contract_version(deps.as_mut().storage, "crates.io:other", "0.0.1").unwrap();
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InvalidContractName {
+                expected: CONTRACT_NAME.to_owned(),
+                actual: "crates.io:other".to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_from_first_version() {
+        // Storage as the first version left it, without any cw2 info.
+        let mut deps = mock_dependencies();
+        deps.querier.update_wasm(|query| match query {
+            WasmQuery::ContractInfo { .. } => {
+                let mut info = ContractInfoResponse::default();
+                info.admin = Some("wasm_admin".to_owned());
+                SystemResult::Ok(ContractResult::Ok(to_binary(&info).unwrap()))
+            }
+            _ => unimplemented!(),
+        });
+        let admins = vec![
+            Addr::unchecked("admin1"),
+            Addr::unchecked("admin2"),
+            Addr::unchecked("admin1"),
+        ];
+        crate::state::LEGACY_ADMINS
+            .save(deps.as_mut().storage, &admins)
+            .unwrap();
+        crate::state::LEGACY_DONATION_DENOM
+            .save(deps.as_mut().storage, &"eth".to_owned())
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let resp: cw2::ContractVersion =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::ContractVersion {}).unwrap())
+                .unwrap();
+        assert_eq!(resp.version, CONTRACT_VERSION);
+
+        let resp: AdminsListResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            resp.admins
+                .iter()
+                .map(|admin| (admin.addr.as_str(), admin.weight))
+                .collect::<Vec<_>>(),
+            vec![("admin1", 1), ("admin2", 1)]
+        );
+
+        let resp: cw4::TotalWeightResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::TotalWeight { at_height: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(resp.weight, 2);
+
+        let resp: DonationDenomsResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::DonationDenoms {}).unwrap())
+                .unwrap();
+        assert_eq!(resp.denoms, vec!["eth".to_owned()]);
+
+        let resp: HasRoleResp = from_binary(
+            &query(
+                deps.as_ref(),
+                mock_env(),
+                QueryMsg::HasRole {
+                    addr: "wasm_admin".to_owned(),
+                    role: Role::Owner,
                 },
-                &[],
-                "Contract 2",
-                None,
             )
+            .unwrap(),
+        )
+        .unwrap();
+        assert!(resp.has_role);
+
+        // The first version never stored a ledger or a payout mode.
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &coins(10, "eth")),
+            ExecuteMsg::Donate {},
+        )
+        .unwrap();
+        assert!(resp
+            .attributes
+            .iter()
+            .any(|attr| attr.key == "donation_id" && attr.value == "1"));
+    }
+
+    #[test]
+    fn migrate_moves_weights_to_cw4_keys() {
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg::default(),
+        )
+        .unwrap();
+        // Weights and their history as kept before they moved to the cw4 keys.
+        let admin = Addr::unchecked("admin1");
+        crate::state::LEGACY_ADMIN_WEIGHTS
+            .save(deps.as_mut().storage, &admin, &2, 10)
+            .unwrap();
+        crate::state::LEGACY_TOTAL_WEIGHT
+            .save(deps.as_mut().storage, &2, 10)
+            .unwrap();
+        crate::state::ADMIN_COUNT
+            .save(deps.as_mut().storage, &1)
             .unwrap();
 
-        let resp: AdminsListResp = app
-            .wrap()
-            .query_wasm_smart(addr, &QueryMsg::AdminsList {})
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        let members: Map<&Addr, u64> = Map::new(cw4::MEMBERS_KEY);
+        assert_eq!(members.load(&deps.storage, &admin).unwrap(), 2);
+        let total: Item<u64> = Item::new(cw4::TOTAL_KEY);
+        assert_eq!(total.load(&deps.storage).unwrap(), 2);
+
+        let weight = |deps: Deps, at_height| -> Option<u64> {
+            let resp: cw4::MemberResponse = from_binary(
+                &query(
+                    deps,
+                    mock_env(),
+                    QueryMsg::Member {
+                        addr: "admin1".to_owned(),
+                        at_height,
+                    },
+                )
+                .unwrap(),
+            )
             .unwrap();
+            resp.weight
+        };
+        assert_eq!(weight(deps.as_ref(), Some(10)), None);
+        assert_eq!(weight(deps.as_ref(), Some(11)), Some(2));
+        assert_eq!(weight(deps.as_ref(), None), Some(2));
+    }
 
-        dbg!(resp.clone());
+    #[test]
+    fn migrate_fills_later_settings() {
+        // An instance from before the removal policy, minimum and ledger existed.
+        let mut deps = mock_dependencies();
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner", &[]),
+            InstantiateMsg {
+                admins: vec![member("admin1", 1), member("admin2", 1)],
+                donation_denoms: vec!["eth".to_owned()],
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        crate::state::REMOVAL_POLICY.remove(deps.as_mut().storage);
+        crate::state::MIN_ADMINS.remove(deps.as_mut().storage);
+        crate::state::DONATION_COUNT.remove(deps.as_mut().storage);
 
-        assert_eq!(
-            resp,
-            AdminsListResp {
-                admins: vec![Addr::unchecked("admin1"), Addr::unchecked("admin2")],
-            }
-        );
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("user", &coins(10, "eth")),
+            ExecuteMsg::Donate {},
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::RemoveMembers {
+                admins: vec!["admin2".to_owned()],
+            },
+        )
+        .unwrap();
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin1", &[]),
+            ExecuteMsg::Leave {},
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::TooFewAdmins { min: 1 });
     }
 
     #[test]
-    fn greet_query_with_appchain() {
+    fn sudo_overrides() {
         let mut app = App::default();
 
-        let code = ContractWrapper::new(execute, instantiate, query);
+        let code = ContractWrapper::new(execute, instantiate, query).with_sudo(sudo);
         let code_id = app.store_code(Box::new(code));
 
         let addr = app
@@ -397,8 +4900,9 @@ mod tests {
                 code_id,
                 Addr::unchecked("owner"),
                 &InstantiateMsg {
-                    admins: vec![],
-                    donation_denom: "eth".to_owned(),
+                    admins: vec![member("admin1", 1), member("admin2", 1)],
+                    donation_denoms: vec!["eth".to_owned()],
+                    ..Default::default()
                 },
                 &[],
                 "Contract",
@@ -406,43 +4910,57 @@ mod tests {
             )
             .unwrap();
 
-        let resp: GreetResp = app
-            .wrap()
-            .query_wasm_smart(addr, &QueryMsg::Greet {})
+        let resp = app
+            .wasm_sudo(
+                addr.clone(),
+                &SudoMsg::ReplaceAdmins {
+                    admins: vec![member("admin2", 3), member("admin3", 1)],
+                },
+            )
             .unwrap();
 
+        assert_eq!(events::parse::<AdminRemoved>(&resp.events).len(), 1);
+
+        let resp: AdminsListResp = app
+            .wrap()
+            .query_wasm_smart(
+                &addr,
+                &QueryMsg::AdminsList {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
         assert_eq!(
-            resp,
-            GreetResp {
-                message: "Hello World".to_owned()
-            }
+            resp.admins,
+            vec![
+                AdminResp {
+                    addr: Addr::unchecked("admin2"),
+                    weight: 3,
+                    share: Decimal::percent(75),
+                    expires: None,
+                },
+                AdminResp {
+                    addr: Addr::unchecked("admin3"),
+                    weight: 1,
+                    share: Decimal::percent(25),
+                    expires: None,
+                },
+            ]
         );
-    }
-
-    #[test]
-    fn greet_query_with_mock() {
-        let mut deps = mock_dependencies();
-        let env = mock_env();
 
-        instantiate(
-            deps.as_mut(),
-            env.clone(),
-            mock_info("sender", &[]),
-            InstantiateMsg {
-                admins: vec![],
-                donation_denom: "eth".to_owned(),
+        app.wasm_sudo(
+            addr.clone(),
+            &SudoMsg::SetDonationDenoms {
+                denoms: vec!["atom".to_owned()],
             },
         )
         .unwrap();
 
-        let resp = query(deps.as_ref(), env, QueryMsg::Greet {}).unwrap();
-        let resp: GreetResp = from_binary(&resp).unwrap();
-
-        assert_eq!(
-            resp,
-            GreetResp {
-                message: "Hello World".to_owned()
-            }
-        );
+        let resp: DonationDenomsResp = app
+            .wrap()
+            .query_wasm_smart(&addr, &QueryMsg::DonationDenoms {})
+            .unwrap();
+        assert_eq!(resp.denoms, vec!["atom".to_owned()]);
     }
 }
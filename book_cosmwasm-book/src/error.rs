@@ -1,7 +1,10 @@
 use cosmwasm_std::{Addr, StdError};
-use cw_utils::PaymentError;
+use cw_controllers::HookError;
+use cw_utils::{Expiration, PaymentError};
 use thiserror::Error;
 
+use crate::msg::Role;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
     #[error("{0}")]
@@ -10,6 +13,84 @@ pub enum ContractError {
     #[error("{sender} is not contract admin")]
     Unauthorized { sender: Addr },
 
+    #[error("The {role} role follows admin membership and cannot be assigned directly")]
+    DerivedRole { role: Role },
+
+    #[error("The last owner cannot give up the owner role")]
+    LastOwner {},
+
     #[error("Payment error: {0}")]
     Payment(#[from] PaymentError),
+
+    #[error("{0}")]
+    Hook(#[from] HookError),
+
+    #[error("{addr} is not an admin")]
+    NotAdmin { addr: Addr },
+
+    #[error("There are no admins to receive the donation")]
+    NoAdmins {},
+
+    #[error("Donations in {denom} are not accepted")]
+    UnsupportedDenom { denom: String },
+
+    #[error("{addr} has no rewards to claim")]
+    NothingToClaim { addr: Addr },
+
+    #[error("Donations in cw20 token {token} are not accepted")]
+    UnsupportedToken { token: Addr },
+
+    #[error("Cannot migrate from contract {actual}, expected {expected}")]
+    InvalidContractName { expected: String, actual: String },
+
+    #[error("Invalid contract version {version}: {reason}")]
+    InvalidVersion { version: String, reason: String },
+
+    #[error("Cannot migrate from version {stored} down to {current}")]
+    Downgrade { stored: String, current: String },
+
+    #[error("Vesting duration must be positive and not shorter than the cliff")]
+    InvalidVesting {},
+
+    #[error("Invalid voting threshold")]
+    InvalidThreshold {},
+
+    #[error("Voting period must not be zero")]
+    InvalidVotingPeriod {},
+
+    #[error("Membership changes must go through a proposal")]
+    ProposalRequired {},
+
+    #[error("Voting is not enabled for this group")]
+    VotingDisabled {},
+
+    #[error("Proposal {id} not found")]
+    ProposalNotFound { id: u64 },
+
+    #[error("Proposal {id} is not open for voting")]
+    ProposalNotOpen { id: u64 },
+
+    #[error("Proposal {id} has expired")]
+    ProposalExpired { id: u64 },
+
+    #[error("{voter} has already voted on proposal {id}")]
+    AlreadyVoted { id: u64, voter: Addr },
+
+    #[error("{voter} was not an admin when proposal {id} was created")]
+    NotEligible { id: u64, voter: Addr },
+
+    #[error("{addr} is listed more than once")]
+    DuplicateAdmin { addr: Addr },
+
+    #[error("{addr} is already an admin")]
+    AlreadyAdmin { addr: Addr },
+
+    #[error("The group has to keep at least {min} admins")]
+    TooFewAdmins { min: u64 },
+
+    #[error("Only an empty group can be re-seeded")]
+    GroupNotEmpty {},
+
+    #[error("Membership expiration {expires} has already passed")]
+    InvalidExpiration { expires: Expiration },
 }
@@ -0,0 +1,186 @@
+use cosmwasm_std::{Addr, Attribute, Event};
+
+use crate::msg::Role;
+
+/// A custom event emitted by the contract, convertible from and back into a
+/// raw `Event`.
+pub trait ContractEvent: Into<Event> + Sized {
+    /// Event type as set by the contract. The chain reports it prefixed with `wasm-`.
+    const TYPE: &'static str;
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self>;
+}
+
+/// Collects every event of type `T` out of `events`, as found in a transaction
+/// result or in cw-multi-test's `AppResponse::events`.
+pub fn parse<T: ContractEvent>(events: &[Event]) -> Vec<T> {
+    events
+        .iter()
+        .filter(|event| event.ty.strip_prefix("wasm-").unwrap_or(&event.ty) == T::TYPE)
+        .filter_map(|event| T::from_attributes(&event.attributes))
+        .collect()
+}
+
+fn attribute<'a>(attributes: &'a [Attribute], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+}
+
+fn role(value: &str) -> Option<Role> {
+    [
+        Role::Owner,
+        Role::MembershipManager,
+        Role::Treasurer,
+        Role::Member,
+    ]
+    .into_iter()
+    .find(|role| role.as_str() == value)
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct AdminAdded {
+    pub addr: Addr,
+}
+
+impl From<AdminAdded> for Event {
+    fn from(event: AdminAdded) -> Self {
+        Event::new(AdminAdded::TYPE).add_attribute("addr", event.addr)
+    }
+}
+
+impl ContractEvent for AdminAdded {
+    const TYPE: &'static str = "admin_added";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct AdminRemoved {
+    pub addr: Addr,
+}
+
+impl From<AdminRemoved> for Event {
+    fn from(event: AdminRemoved) -> Self {
+        Event::new(AdminRemoved::TYPE).add_attribute("addr", event.addr)
+    }
+}
+
+impl ContractEvent for AdminRemoved {
+    const TYPE: &'static str = "admin_removed";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+        })
+    }
+}
+
+/// An admin dropped because their membership lapsed.
+#[derive(PartialEq, Debug, Clone)]
+pub struct AdminExpired {
+    pub addr: Addr,
+}
+
+impl From<AdminExpired> for Event {
+    fn from(event: AdminExpired) -> Self {
+        Event::new(AdminExpired::TYPE).add_attribute("addr", event.addr)
+    }
+}
+
+impl ContractEvent for AdminExpired {
+    const TYPE: &'static str = "admin_expired";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+        })
+    }
+}
+
+/// A `RemoveMembers` request recorded without reaching the removal policy yet.
+#[derive(PartialEq, Debug, Clone)]
+pub struct RemovalApproved {
+    pub addr: Addr,
+    pub approver: Addr,
+    pub approvals: u64,
+    pub required: u64,
+}
+
+impl From<RemovalApproved> for Event {
+    fn from(event: RemovalApproved) -> Self {
+        Event::new(RemovalApproved::TYPE)
+            .add_attribute("addr", event.addr)
+            .add_attribute("approver", event.approver)
+            .add_attribute("approvals", event.approvals.to_string())
+            .add_attribute("required", event.required.to_string())
+    }
+}
+
+impl ContractEvent for RemovalApproved {
+    const TYPE: &'static str = "removal_approved";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+            approver: Addr::unchecked(attribute(attributes, "approver")?),
+            approvals: attribute(attributes, "approvals")?.parse().ok()?,
+            required: attribute(attributes, "required")?.parse().ok()?,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct RoleGranted {
+    pub addr: Addr,
+    pub role: Role,
+}
+
+impl From<RoleGranted> for Event {
+    fn from(event: RoleGranted) -> Self {
+        Event::new(RoleGranted::TYPE)
+            .add_attribute("addr", event.addr)
+            .add_attribute("role", event.role.as_str())
+    }
+}
+
+impl ContractEvent for RoleGranted {
+    const TYPE: &'static str = "role_granted";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+            role: role(attribute(attributes, "role")?)?,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct RoleRevoked {
+    pub addr: Addr,
+    pub role: Role,
+}
+
+impl From<RoleRevoked> for Event {
+    fn from(event: RoleRevoked) -> Self {
+        Event::new(RoleRevoked::TYPE)
+            .add_attribute("addr", event.addr)
+            .add_attribute("role", event.role.as_str())
+    }
+}
+
+impl ContractEvent for RoleRevoked {
+    const TYPE: &'static str = "role_revoked";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            addr: Addr::unchecked(attribute(attributes, "addr")?),
+            role: role(attribute(attributes, "role")?)?,
+        })
+    }
+}
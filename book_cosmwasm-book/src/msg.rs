@@ -1,24 +1,229 @@
 use cosmwasm_schema::QueryResponses;
-use cosmwasm_std::Addr;
+use cosmwasm_std::{Addr, Coin, Decimal, Timestamp};
+use cw20::Cw20ReceiveMsg;
+use cw_utils::{Duration, Expiration};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 // init
-#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct InstantiateMsg {
-    pub admins: Vec<String>,
-    pub donation_denom: String,
+    pub admins: Vec<Member>,
+    pub donation_denoms: Vec<String>,
+    /// When set, membership changes must go through `Propose`/`Vote`
+    /// instead of a direct `AddMembers`.
+    pub voting: Option<VotingConfig>,
+    #[serde(default)]
+    pub payout_mode: PayoutMode,
+    /// cw20 token contracts accepted through `Receive`.
+    #[serde(default)]
+    pub cw20_tokens: Vec<String>,
+    /// Who has to agree before `RemoveMembers` ejects an admin.
+    #[serde(default)]
+    pub removal_policy: RemovalPolicy,
+    /// Fewest admins `Leave` and `RemoveMembers` may leave behind; defaults to 1.
+    #[serde(default)]
+    pub min_admins: Option<u64>,
+    /// May re-seed the admin set with `Reseed` once it is empty, next to the
+    /// contract's wasm admin.
+    #[serde(default)]
+    pub guardian: Option<String>,
+    /// Receives the `owner` role; defaults to the instantiating address.
+    #[serde(default)]
+    pub owner: Option<String>,
+}
+
+/// Named permission sets. `owner` may do everything the other roles can.
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Role {
+    /// Grants and revokes roles.
+    Owner,
+    /// Adds members and changes their weights and expiry.
+    MembershipManager,
+    /// Manages the accepted donation denoms and cw20 tokens.
+    Treasurer,
+    /// Receives donations, votes and takes part in removals. Held by every
+    /// admin in `ADMINS`, so it cannot be granted or revoked directly.
+    Member,
+}
+
+impl Role {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::MembershipManager => "membership_manager",
+            Role::Treasurer => "treasurer",
+            Role::Member => "member",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutMode {
+    /// Every donation is sent out to the admins right away.
+    #[default]
+    Push,
+    /// Donations accrue per unit of weight and admins withdraw them with `Claim`.
+    Pull,
+    /// Every share is locked into a linear stream, counted in seconds from the
+    /// donation; admins withdraw what has vested with `WithdrawVested`.
+    Vesting { cliff: u64, duration: u64 },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, Default, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum RemovalPolicy {
+    /// A single admin can remove anyone.
+    #[default]
+    AnyAdmin,
+    /// More than half of the admins have to ask for the removal.
+    Majority,
+    /// Every admin except the one being removed has to ask for the removal.
+    AllButTarget,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Member {
+    pub addr: String,
+    /// Relative share of every donation; an admin with weight 0 receives nothing.
+    pub weight: u64,
+    /// When the membership lapses; never if unset.
+    #[serde(default)]
+    pub expires: Option<Expiration>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VotingConfig {
+    pub threshold: Threshold,
+    /// How long a proposal stays open, counted from the block it was created in.
+    pub max_voting_period: Duration,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Threshold {
+    /// Passes once this many admins voted yes.
+    AbsoluteCount { count: u64 },
+    /// Passes once this fraction of the admins (as of proposal creation) voted yes.
+    Percentage { percentage: Decimal },
 }
+
 // execute
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ExecuteMsg {
-    AddMembers { admins: Vec<String> },
+    AddMembers {
+        admins: Vec<Member>,
+    },
     Donate {},
+    /// cw20 `Send` hook; the embedded message is a `ReceiveMsg`.
+    Receive(Cw20ReceiveMsg),
     Leave {},
+    /// Removes `admins`, or records the sender's approval until the removal
+    /// policy is satisfied.
+    RemoveMembers {
+        admins: Vec<String>,
+    },
+    /// Recovery for a group that lost all its admins; only the guardian or
+    /// the wasm contract admin may send it.
+    Reseed {
+        admins: Vec<Member>,
+    },
+    /// Registers a contract to receive a cw4 `MemberChangedHookMsg` on every
+    /// membership change.
+    AddHook {
+        addr: String,
+    },
+    RemoveHook {
+        addr: String,
+    },
+    GrantRole {
+        addr: String,
+        role: Role,
+    },
+    RevokeRole {
+        addr: String,
+        role: Role,
+    },
+    /// Moves the expiry of an admin's membership; `None` makes it permanent.
+    RenewMembership {
+        addr: String,
+        expires: Option<Expiration>,
+    },
+    UpdateWeights {
+        admins: Vec<Member>,
+    },
+    UpdateDenoms {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    UpdateCw20Tokens {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    Claim {},
+    /// Withdraws what has vested from up to `limit` of the sender's streams,
+    /// oldest first.
+    WithdrawVested {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Propose {
+        action: ProposalAction,
+    },
+    Vote {
+        proposal_id: u64,
+        vote: VoteOption,
+    },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReceiveMsg {
+    Donate {},
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalAction {
+    AddMembers { admins: Vec<Member> },
+    RemoveMembers { admins: Vec<String> },
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteOption {
+    Yes,
+    No,
 }
 
+// sudo
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SudoMsg {
+    /// Replaces the whole admin set, bypassing admin signatures and proposals.
+    ReplaceAdmins { admins: Vec<Member> },
+    /// Replaces the set of accepted native donation denoms.
+    SetDonationDenoms { denoms: Vec<String> },
+}
+
+// migrate
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {}
+
 // query
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -26,15 +231,91 @@ pub enum ExecuteMsg {
 pub enum QueryMsg {
     #[returns(GreetResp)]
     Greet {},
+    #[returns(cw2::ContractVersion)]
+    ContractVersion {},
     #[returns(AdminsListResp)]
-    AdminsList {},
+    AdminsList {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(ProposalResp)]
+    Proposal { proposal_id: u64 },
+    #[returns(ProposalListResp)]
+    ListProposals {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(VoteListResp)]
+    ListVotes {
+        proposal_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    #[returns(DustResp)]
+    Dust {},
+    #[returns(DonationDenomsResp)]
+    DonationDenoms {},
+    #[returns(Cw20TokensResp)]
+    Cw20Tokens {},
+    #[returns(PendingRewardsResp)]
+    PendingRewards { addr: String },
+    /// Vesting totals over up to `limit` of the admin's streams, oldest first.
+    #[returns(VestingResp)]
+    Vesting {
+        addr: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    /// cw4: the group owner, the lowest address if there are several.
+    #[returns(cw4::AdminResponse)]
+    Admin {},
+    /// cw4: the admin's weight, optionally as of the start of block `at_height`.
+    #[returns(cw4::MemberResponse)]
+    Member {
+        addr: String,
+        at_height: Option<u64>,
+    },
+    /// cw4: the admins with their weights.
+    #[returns(cw4::MemberListResponse)]
+    ListMembers {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// cw4: the sum of admin weights, optionally as of the start of block `at_height`.
+    #[returns(cw4::TotalWeightResponse)]
+    TotalWeight { at_height: Option<u64> },
+    /// cw4: the contracts notified of membership changes.
+    #[returns(cw4::HooksResponse)]
+    Hooks {},
+    #[returns(HasRoleResp)]
+    HasRole { addr: String, role: Role },
+    /// Recorded donations, oldest first, optionally narrowed to one donor
+    /// and an inclusive block height range.
+    #[returns(DonationHistoryResp)]
+    DonationHistory {
+        donor: Option<String>,
+        min_height: Option<u64>,
+        max_height: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 // query responses
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct AdminsListResp {
-    pub admins: Vec<Addr>,
+    pub admins: Vec<AdminResp>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct AdminResp {
+    pub addr: Addr,
+    pub weight: u64,
+    /// Fraction of each donation this admin receives.
+    pub share: Decimal,
+    pub expires: Option<Expiration>,
 }
 #[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
 #[serde(rename_all = "snake_case")]
@@ -42,6 +323,127 @@ pub struct GreetResp {
     pub message: String,
 }
 
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DonationDenomsResp {
+    pub denoms: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct Cw20TokensResp {
+    pub tokens: Vec<Addr>,
+}
+
+/// cw20 rewards are reported with a `cw20:<token address>` denom.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PendingRewardsResp {
+    pub rewards: Vec<Coin>,
+}
+
+/// Donation remainders held back by integer division, rolled into the next donation.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DustResp {
+    pub dust: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Debug, Clone, Copy, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProposalStatus {
+    Open,
+    Executed,
+    Rejected,
+    Expired,
+}
+
+impl fmt::Display for ProposalStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status = match self {
+            ProposalStatus::Open => "open",
+            ProposalStatus::Executed => "executed",
+            ProposalStatus::Rejected => "rejected",
+            ProposalStatus::Expired => "expired",
+        };
+        f.write_str(status)
+    }
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalResp {
+    pub id: u64,
+    pub proposer: Addr,
+    pub action: ProposalAction,
+    pub status: ProposalStatus,
+    pub expires: Expiration,
+    pub threshold: Threshold,
+    pub total_admins: u64,
+    pub yes: u64,
+    pub no: u64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProposalListResp {
+    pub proposals: Vec<ProposalResp>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoteResp {
+    pub voter: Addr,
+    pub vote: VoteOption,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VoteListResp {
+    pub votes: Vec<VoteResp>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct VestingResp {
+    /// Vested and not yet withdrawn.
+    pub vested: Vec<Coin>,
+    pub unvested: Vec<Coin>,
+    /// Last stream covered, to pass as `start_after` for the next page.
+    pub last_stream: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct HasRoleResp {
+    pub has_role: bool,
+}
+
+/// Part of a donation sent to a single admin.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DonationSplit {
+    pub recipient: Addr,
+    pub coins: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DonationResp {
+    pub id: u64,
+    pub donor: Addr,
+    pub coins: Vec<Coin>,
+    pub height: u64,
+    pub time: Timestamp,
+    pub splits: Vec<DonationSplit>,
+}
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct DonationHistoryResp {
+    pub donations: Vec<DonationResp>,
+}
+
 /*
 use cosmwasm_std::Addr;
 use cosmwasm_schema::cw_serde
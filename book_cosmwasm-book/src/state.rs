@@ -1,5 +1,178 @@
-use cosmwasm_std::Addr;
-use cw_storage_plus::Item;
+use cosmwasm_std::{Addr, Coin, Decimal256, Empty, Timestamp, Uint128};
+use cw_controllers::Hooks;
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap, Strategy,
+};
+use cw_utils::Expiration;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 
-pub const ADMINS: Item<Vec<Addr>> = Item::new("admins");
-pub const DONATION_DENOM: Item<String> = Item::new("donation_denom");
+use crate::msg::{
+    DonationSplit, PayoutMode, ProposalAction, ProposalStatus, RemovalPolicy, Threshold,
+    VoteOption, VotingConfig,
+};
+
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct Proposal {
+    pub proposer: Addr,
+    pub action: ProposalAction,
+    pub status: ProposalStatus,
+    pub expires: Expiration,
+    /// Height the proposal was created at; only admins of that height may vote.
+    pub start_height: u64,
+    pub threshold: Threshold,
+    pub total_admins: u64,
+    pub yes: u64,
+    pub no: u64,
+}
+
+/// Rewards an admin accrued in pull mode, per denom.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, Default, JsonSchema)]
+pub struct RewardInfo {
+    /// Value of the global reward index at the last settlement.
+    pub index: Decimal256,
+    /// Settled but not yet claimed amount, fractions included.
+    pub pending: Decimal256,
+}
+
+/// One admin's share of one donation, released linearly over time.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct VestingStream {
+    pub start: Timestamp,
+    pub cliff: u64,
+    pub duration: u64,
+    pub total: Vec<Coin>,
+    /// Already withdrawn, per coin in `total`.
+    pub withdrawn: Vec<Uint128>,
+}
+
+impl VestingStream {
+    /// Amount of every coin in `total` vested by `now`, withdrawn or not.
+    pub fn vested(&self, now: Timestamp) -> Vec<Uint128> {
+        let elapsed = now.seconds().saturating_sub(self.start.seconds());
+        self.total
+            .iter()
+            .map(|coin| {
+                if elapsed < self.cliff {
+                    Uint128::zero()
+                } else if elapsed >= self.duration {
+                    coin.amount
+                } else {
+                    coin.amount.multiply_ratio(elapsed, self.duration)
+                }
+            })
+            .collect()
+    }
+}
+
+/// A single donation as recorded in the ledger.
+#[derive(Serialize, Deserialize, PartialEq, Debug, Clone, JsonSchema)]
+pub struct DonationRecord {
+    pub donor: Addr,
+    pub coins: Vec<Coin>,
+    pub height: u64,
+    pub time: Timestamp,
+    /// What every admin was sent; empty in pull mode, where admins claim later.
+    pub splits: Vec<DonationSplit>,
+}
+
+pub struct DonationIndexes<'a> {
+    pub donor: MultiIndex<'a, Addr, DonationRecord, u64>,
+    pub height: MultiIndex<'a, u64, DonationRecord, u64>,
+}
+
+impl<'a> IndexList<DonationRecord> for DonationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DonationRecord>> + '_> {
+        let v: Vec<&dyn Index<DonationRecord>> = vec![&self.donor, &self.height];
+        Box::new(v.into_iter())
+    }
+}
+
+/// Donation ledger keyed by a sequential id, indexed by donor and block height.
+pub fn donations<'a>() -> IndexedMap<'a, u64, DonationRecord, DonationIndexes<'a>> {
+    let indexes = DonationIndexes {
+        donor: MultiIndex::new(
+            |_, record: &DonationRecord| record.donor.clone(),
+            "donations",
+            "donations__donor",
+        ),
+        height: MultiIndex::new(
+            |_, record: &DonationRecord| record.height,
+            "donations",
+            "donations__height",
+        ),
+    };
+    IndexedMap::new("donations", indexes)
+}
+
+pub const DONATION_COUNT: Item<u64> = Item::new("donation_count");
+
+/// Admins keyed by address, holding each admin's donation weight. Stored under
+/// the cw4 keys so cw4 helpers can read it raw, and snapshotted every block so
+/// cw4 consumers can query the membership at a past height.
+pub const ADMINS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    cw4::MEMBERS_KEY,
+    cw4::MEMBERS_CHECKPOINTS,
+    cw4::MEMBERS_CHANGELOG,
+    Strategy::EveryBlock,
+);
+/// Expiry of every admin whose membership is time-limited.
+pub const ADMIN_EXPIRY: Map<&Addr, Expiration> = Map::new("admin_expiry");
+/// The same expiries keyed by the height, or the block time in nanoseconds,
+/// they lapse at, so lapsed admins are found without scanning every expiry.
+pub const EXPIRING_AT_HEIGHT: Map<(u64, &Addr), Empty> = Map::new("expiring_at_height");
+pub const EXPIRING_AT_TIME: Map<(u64, &Addr), Empty> = Map::new("expiring_at_time");
+pub const ADMIN_COUNT: Item<u64> = Item::new("admin_count");
+pub const MIN_ADMINS: Item<u64> = Item::new("min_admins");
+/// Explicitly granted roles, keyed by (holder, role name).
+pub const ROLES: Map<(&Addr, &str), Empty> = Map::new("roles");
+/// Contracts notified with a cw4 `MemberChangedHookMsg` on every membership change.
+pub const HOOKS: Hooks = Hooks::new("hooks");
+/// Address allowed to re-seed an empty admin set.
+pub const GUARDIAN: Item<Addr> = Item::new("guardian");
+pub const TOTAL_WEIGHT: SnapshotItem<u64> = SnapshotItem::new(
+    cw4::TOTAL_KEY,
+    cw4::TOTAL_KEY_CHECKPOINTS,
+    cw4::TOTAL_KEY_CHANGELOG,
+    Strategy::EveryBlock,
+);
+/// `ADMINS` and `TOTAL_WEIGHT` as stored before they moved to the cw4 keys,
+/// only read by `migrate`.
+pub const LEGACY_ADMIN_WEIGHTS: SnapshotMap<&Addr, u64> = SnapshotMap::new(
+    "admins",
+    "admins__checkpoints",
+    "admins__changelog",
+    Strategy::EveryBlock,
+);
+pub const LEGACY_TOTAL_WEIGHT: SnapshotItem<u64> = SnapshotItem::new(
+    "total_weight",
+    "total_weight__checkpoints",
+    "total_weight__changelog",
+    Strategy::EveryBlock,
+);
+pub const DONATION_DENOMS: Map<&str, Empty> = Map::new("donation_denoms");
+/// Admin list and donation denom of the first version, only read by `migrate`.
+pub const LEGACY_ADMINS: Item<Vec<Addr>> = Item::new("admins");
+pub const LEGACY_DONATION_DENOM: Item<String> = Item::new("donation_denom");
+/// cw20 token contracts accepted as donations.
+pub const CW20_TOKENS: Map<&Addr, Empty> = Map::new("cw20_tokens");
+/// Prefix of the pseudo-denom a cw20 token is keyed by in `DUST` and the reward maps.
+pub const CW20_PREFIX: &str = "cw20:";
+/// Per-denom remainder left over after splitting a donation.
+pub const DUST: Map<&str, Uint128> = Map::new("dust");
+
+pub const PAYOUT_MODE: Item<PayoutMode> = Item::new("payout_mode");
+/// Vesting streams keyed by (admin, donation id).
+pub const VESTING: Map<(&Addr, u64), VestingStream> = Map::new("vesting");
+/// Cumulative amount donated per unit of weight, per denom.
+pub const REWARD_INDEX: Map<&str, Decimal256> = Map::new("reward_index");
+pub const ADMIN_REWARDS: Map<(&Addr, &str), RewardInfo> = Map::new("admin_rewards");
+
+pub const REMOVAL_POLICY: Item<RemovalPolicy> = Item::new("removal_policy");
+/// Admins asking for another admin's removal, keyed by (target, approver).
+pub const REMOVAL_APPROVALS: Map<(&Addr, &Addr), Empty> = Map::new("removal_approvals");
+
+pub const VOTING: Item<VotingConfig> = Item::new("voting");
+pub const PROPOSAL_COUNT: Item<u64> = Item::new("proposal_count");
+pub const PROPOSALS: Map<u64, Proposal> = Map::new("proposals");
+pub const VOTES: Map<(u64, &Addr), VoteOption> = Map::new("votes");
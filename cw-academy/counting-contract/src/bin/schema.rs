@@ -1,10 +1,12 @@
 use cosmwasm_schema::write_api;
-use counting_contract::msg::{ExecMsg, InstantiateMsg, QueryMsg};
+use counting_contract::msg::{ExecMsg, InstantiateMsg, MigrateMsg, QueryMsg, SudoMsg};
 
 fn main() {
     write_api! {
         instantiate: InstantiateMsg,
         execute: ExecMsg,
         query: QueryMsg,
+        migrate: MigrateMsg,
+        sudo: SudoMsg,
     }
 }
@@ -1,73 +1,683 @@
 use crate::{
+    error::ContractError,
     msg::InstantiateMsg,
-    state::{COUNTER, MINIMAL_DONATION, OWNER},
+    state::{
+        COUNTER, DONATION_COUNT, LEGACY_MINIMAL_DONATION, MINIMAL_DONATIONS, OWNER, RESET_COUNT,
+        UNDER_MINIMUM_POLICY,
+    },
 };
-use cosmwasm_std::{DepsMut, MessageInfo, Response, StdResult};
+use cosmwasm_std::{DepsMut, MessageInfo, Response};
+use cw2::ContractVersion;
 
-pub fn instantiate(deps: DepsMut, info: MessageInfo, msg: InstantiateMsg) -> StdResult<Response> {
+const CONTRACT_NAME: &str = concat!("crates.io:", env!("CARGO_PKG_NAME"));
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub fn instantiate(
+    deps: DepsMut,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
     COUNTER.save(deps.storage, &0)?; // init value as 0
-    MINIMAL_DONATION.save(deps.storage, &msg.minimal_donation)?;
+    exec::save_thresholds(deps.storage, msg.minimal_donations)?;
+    UNDER_MINIMUM_POLICY.save(deps.storage, &msg.under_minimum)?;
     OWNER.save(deps.storage, &info.sender)?;
+    DONATION_COUNT.save(deps.storage, &0)?;
+    RESET_COUNT.save(deps.storage, &0)?;
 
     Ok(Response::new())
 }
 
+pub fn migrate(deps: DepsMut) -> Result<Response, ContractError> {
+    // the first deployments stored no cw2 info; treat them as the oldest version
+    let stored = cw2::CONTRACT
+        .may_load(deps.storage)?
+        .unwrap_or_else(|| ContractVersion {
+            contract: CONTRACT_NAME.to_owned(),
+            version: "0.0.0".to_owned(),
+        });
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_owned(),
+            actual: stored.contract,
+        });
+    }
+
+    let parse = |version: &str| {
+        version
+            .parse::<semver::Version>()
+            .map_err(|err| ContractError::InvalidVersion {
+                version: version.to_owned(),
+                reason: err.to_string(),
+            })
+    };
+    if parse(&stored.version)? > parse(CONTRACT_VERSION)? {
+        return Err(ContractError::Downgrade {
+            stored: stored.version,
+            current: CONTRACT_VERSION.to_owned(),
+        });
+    }
+
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    if let Some(minimal_donation) = LEGACY_MINIMAL_DONATION.may_load(deps.storage)? {
+        MINIMAL_DONATIONS.save(
+            deps.storage,
+            &minimal_donation.denom,
+            &minimal_donation.amount,
+        )?;
+        LEGACY_MINIMAL_DONATION.remove(deps.storage);
+    }
+
+    let resp = Response::new()
+        .add_attribute("action", "migrate")
+        .add_attribute("from_version", stored.version)
+        .add_attribute("to_version", CONTRACT_VERSION);
+
+    Ok(resp)
+}
+
+pub mod sudo {
+    use crate::{
+        events::{CounterSet, OwnerSet},
+        state::{COUNTER, OWNER, PENDING_OWNER},
+    };
+    use cosmwasm_std::{DepsMut, Response, StdResult};
+
+    pub fn set_owner(deps: DepsMut, owner: String) -> StdResult<Response> {
+        let owner = deps.api.addr_validate(&owner)?;
+        OWNER.save(deps.storage, &owner)?;
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "sudo_set_owner")
+            .add_event(OwnerSet { owner }.into());
+
+        Ok(resp)
+    }
+
+    pub fn set_counter(deps: DepsMut, value: u64) -> StdResult<Response> {
+        COUNTER.save(deps.storage, &value)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "sudo_set_counter")
+            .add_event(CounterSet { counter: value }.into());
+
+        Ok(resp)
+    }
+}
+
 pub mod query {
-    use crate::{msg::ValueResp, state::COUNTER};
-    use cosmwasm_std::{Deps, StdResult};
+    use crate::{
+        msg::{
+            DonationHistoryResp, DonationResp, DonorStatsResp, DonorTallyResp, OwnershipResp,
+            ResetHistoryResp, ResetResp, ThresholdsResp, TopDonorsResp, ValueResp,
+        },
+        state::{
+            donations, donor_tallies, DonationRecord, DonorTally, COUNTER, MINIMAL_DONATIONS,
+            OWNER, PENDING_OWNER, RESETS, UNDER_MINIMUM_POLICY,
+        },
+    };
+    use cosmwasm_std::{Coin, Deps, Env, Order, StdResult};
+    use cw2::ContractVersion;
+    use cw_storage_plus::Bound;
+
+    const DEFAULT_LIMIT: u32 = 10;
+    const MAX_LIMIT: u32 = 30;
 
     pub fn value(deps: Deps) -> StdResult<ValueResp> {
         let value = COUNTER.load(deps.storage)?;
 
         Ok(ValueResp { value })
     }
+
+    pub fn contract_version(deps: Deps) -> StdResult<ContractVersion> {
+        cw2::get_contract_version(deps.storage)
+    }
+
+    pub fn thresholds(deps: Deps) -> StdResult<ThresholdsResp> {
+        let thresholds = MINIMAL_DONATIONS
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(denom, amount)| Coin { denom, amount }))
+            .collect::<StdResult<_>>()?;
+        let under_minimum = UNDER_MINIMUM_POLICY
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+
+        Ok(ThresholdsResp {
+            thresholds,
+            under_minimum,
+        })
+    }
+
+    pub fn ownership(deps: Deps, env: Env) -> StdResult<OwnershipResp> {
+        let owner = OWNER.may_load(deps.storage)?;
+        // a lapsed offer can no longer be accepted, so it is not reported
+        let pending = PENDING_OWNER.may_load(deps.storage)?.filter(
+            |pending| !matches!(pending.expiry, Some(expiry) if expiry.is_expired(&env.block)),
+        );
+
+        Ok(OwnershipResp {
+            owner,
+            pending_owner: pending.as_ref().map(|pending| pending.owner.clone()),
+            pending_expiry: pending.and_then(|pending| pending.expiry),
+        })
+    }
+
+    pub fn donation_history(
+        deps: Deps,
+        donor: Option<String>,
+        min_height: Option<u64>,
+        max_height: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<DonationHistoryResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let min_height = min_height.unwrap_or_default();
+        let max_height = max_height.unwrap_or(u64::MAX);
+        let ledger = donations();
+
+        // ids grow with block height, so both indexes yield donations oldest first
+        let records: Box<dyn Iterator<Item = StdResult<(u64, DonationRecord)>>> = match donor {
+            Some(donor) => {
+                let donor = deps.api.addr_validate(&donor)?;
+                let start = start_after.map(Bound::exclusive);
+                let records = ledger
+                    .idx
+                    .donor
+                    .prefix(donor)
+                    .range(deps.storage, start, None, Order::Ascending)
+                    .filter(
+                        move |item| !matches!(item, Ok((_, record)) if record.height < min_height),
+                    );
+                Box::new(records)
+            }
+            None => {
+                let start = match start_after {
+                    Some(id) => {
+                        let height = ledger.load(deps.storage, id)?.height;
+                        if height < min_height {
+                            Bound::inclusive((min_height, 0))
+                        } else {
+                            Bound::exclusive((height, id))
+                        }
+                    }
+                    None => Bound::inclusive((min_height, 0)),
+                };
+                let records = ledger.idx.height.range(
+                    deps.storage,
+                    Some(start),
+                    Some(Bound::inclusive((max_height, u64::MAX))),
+                    Order::Ascending,
+                );
+                Box::new(records)
+            }
+        };
+
+        let donations = records
+            .take_while(|item| !matches!(item, Ok((_, record)) if record.height > max_height))
+            .take(limit)
+            .map(|item| {
+                item.map(|(id, record)| DonationResp {
+                    id,
+                    donor: record.donor,
+                    coins: record.coins,
+                    height: record.height,
+                    time: record.time,
+                    counted: record.counted,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonationHistoryResp { donations })
+    }
+
+    fn tally_resp(tally: DonorTally) -> DonorTallyResp {
+        DonorTallyResp {
+            donor: tally.donor,
+            denom: tally.denom,
+            count: tally.count,
+            amount: tally.amount,
+        }
+    }
+
+    pub fn donor_stats(deps: Deps, addr: String) -> StdResult<DonorStatsResp> {
+        let addr = deps.api.addr_validate(&addr)?;
+        let tallies = donor_tallies()
+            .prefix(&addr)
+            .range(deps.storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, tally)| tally_resp(tally)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(DonorStatsResp { tallies })
+    }
+
+    pub fn top_donors(
+        deps: Deps,
+        denom: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    ) -> StdResult<TopDonorsResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let tallies = donor_tallies();
+        // ties on amount are broken by the primary key, so the bound needs both
+        let end = match start_after {
+            Some(donor) => {
+                let donor = deps.api.addr_validate(&donor)?;
+                let tally = tallies.load(deps.storage, (&donor, &denom))?;
+                Some(Bound::exclusive((
+                    tally.amount.u128(),
+                    (donor, denom.clone()),
+                )))
+            }
+            None => None,
+        };
+
+        let donors = tallies
+            .idx
+            .amount
+            .sub_prefix(denom)
+            .range(deps.storage, None, end, Order::Descending)
+            .take(limit)
+            .map(|item| item.map(|(_, tally)| tally_resp(tally)))
+            .collect::<StdResult<_>>()?;
+
+        Ok(TopDonorsResp { donors })
+    }
+
+    pub fn reset_history(
+        deps: Deps,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<ResetHistoryResp> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+        let start = start_after.map(Bound::exclusive);
+
+        let resets = RESETS
+            .range(deps.storage, start, None, Order::Ascending)
+            .take(limit)
+            .map(|item| {
+                item.map(|(id, record)| ResetResp {
+                    id,
+                    previous: record.previous,
+                    value: record.value,
+                    height: record.height,
+                    sender: record.sender,
+                })
+            })
+            .collect::<StdResult<_>>()?;
+
+        Ok(ResetHistoryResp { resets })
+    }
 }
 
 pub mod exec {
     use crate::{
         error::ContractError,
-        state::{COUNTER, MINIMAL_DONATION, OWNER},
+        events::{
+            CounterReset, Donated, OwnershipProposed, OwnershipRenounced, OwnershipTransferred,
+            Withdrawn,
+        },
+        msg::UnderMinimumPolicy,
+        state::{
+            donations, donor_tallies, DonationRecord, DonorTally, PendingOwner, ResetRecord,
+            COUNTER, DONATION_COUNT, MINIMAL_DONATIONS, OWNER, PENDING_OWNER, RESETS, RESET_COUNT,
+            UNDER_MINIMUM_POLICY,
+        },
     };
-    use cosmwasm_std::{BankMsg, DepsMut, Env, MessageInfo, Response, StdResult};
+    use cosmwasm_std::{
+        Addr, BankMsg, Coin, Deps, DepsMut, Env, MessageInfo, Order, Response, StdResult, Storage,
+        Uint128,
+    };
+    use cw_utils::Expiration;
+
+    /// Returns the current owner, failing unless it is `sender`.
+    fn ensure_owner(deps: Deps, sender: &Addr) -> Result<Addr, ContractError> {
+        let owner = OWNER
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoOwner {})?;
+        if *sender != owner {
+            return Err(ContractError::Unauthorized {
+                owner: owner.into_string(),
+            });
+        }
+
+        Ok(owner)
+    }
 
-    pub fn donate(deps: DepsMut, info: MessageInfo) -> StdResult<Response> {
-        let minimal_donation = MINIMAL_DONATION.load(deps.storage)?;
+    pub fn donate(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
         let mut value = COUNTER.load(deps.storage)?;
 
-        if info.funds.iter().any(|coin| {
-            // dbg!(&coin.amount);
-            // dbg!(&minimal_donation.amount);
-            coin.denom == minimal_donation.denom && coin.amount >= minimal_donation.amount
-        }) {
+        let mut counted = false;
+        for coin in &info.funds {
+            let minimal_donation = MINIMAL_DONATIONS.may_load(deps.storage, &coin.denom)?;
+            if matches!(minimal_donation, Some(minimal) if coin.amount >= minimal) {
+                counted = true;
+            }
+        }
+        if counted {
             value += 1;
             COUNTER.save(deps.storage, &(value))?;
+
+            for coin in &info.funds {
+                donor_tallies().update(
+                    deps.storage,
+                    (&info.sender, &coin.denom),
+                    |tally| -> StdResult<_> {
+                        let mut tally = tally.unwrap_or_else(|| DonorTally {
+                            donor: info.sender.clone(),
+                            denom: coin.denom.clone(),
+                            count: 0,
+                            amount: Uint128::zero(),
+                        });
+                        tally.count += 1;
+                        tally.amount += coin.amount;
+                        Ok(tally)
+                    },
+                )?;
+            }
         }
 
-        let resp = Response::new()
-            .add_attribute("action", "poke")
-            .add_attribute("sender", info.sender.as_str())
-            .add_attribute("counter", value.to_string());
+        // contracts instantiated before the policy existed keep such funds
+        let mut refund = None;
+        if !counted && !info.funds.is_empty() {
+            match UNDER_MINIMUM_POLICY
+                .may_load(deps.storage)?
+                .unwrap_or_default()
+            {
+                UnderMinimumPolicy::Keep => {}
+                UnderMinimumPolicy::Refund => refund = Some(info.funds.clone()),
+                UnderMinimumPolicy::Reject => return Err(ContractError::BelowMinimum {}),
+            }
+        }
+
+        // a poke without funds is not a donation, and refunded funds never stay,
+        // so neither goes into the ledger
+        let mut donation_id = None;
+        if !info.funds.is_empty() && refund.is_none() {
+            // contracts instantiated before the ledger existed start it here
+            let id = DONATION_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+            DONATION_COUNT.save(deps.storage, &id)?;
+            let record = DonationRecord {
+                donor: info.sender.clone(),
+                coins: info.funds,
+                height: env.block.height,
+                time: env.block.time,
+                counted,
+            };
+            donations().save(deps.storage, id, &record)?;
+            donation_id = Some(id);
+        }
+
+        let mut resp = Response::new().add_attribute("action", "poke");
+        if let Some(amount) = refund {
+            let refunded: Vec<_> = amount.iter().map(Coin::to_string).collect();
+            resp = resp
+                .add_message(BankMsg::Send {
+                    to_address: info.sender.to_string(),
+                    amount,
+                })
+                .add_attribute("refunded", refunded.join(","));
+        }
+        let resp = resp.add_event(
+            Donated {
+                sender: info.sender,
+                counter: value,
+                donation_id,
+            }
+            .into(),
+        );
 
         Ok(resp)
     }
 
-    pub fn withdraw(deps: DepsMut, env: Env, info: MessageInfo) -> Result<Response, ContractError> {
-        let owner = OWNER.load(deps.storage)?;
-        if info.sender != owner {
-            return Err(ContractError::Unauthorized {
-                owner: owner.into_string(),
-            });
+    pub fn withdraw(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        funds: Option<Vec<Coin>>,
+        recipient: Option<String>,
+        reserve: Option<Vec<Coin>>,
+    ) -> Result<Response, ContractError> {
+        let owner = ensure_owner(deps.as_ref(), &info.sender)?;
+        let recipient = match recipient {
+            Some(recipient) => deps.api.addr_validate(&recipient)?,
+            None => owner.clone(),
+        };
+
+        // what may leave the contract once the reserve is set aside
+        let available: Vec<Coin> = deps
+            .querier
+            .query_all_balances(&env.contract.address)?
+            .into_iter()
+            .map(|coin| {
+                let kept: Uint128 = reserve
+                    .iter()
+                    .flatten()
+                    .filter(|reserved| reserved.denom == coin.denom)
+                    .map(|reserved| reserved.amount)
+                    .sum();
+                Coin {
+                    amount: coin.amount.saturating_sub(kept),
+                    denom: coin.denom,
+                }
+            })
+            .collect();
+
+        let amount = match funds {
+            Some(funds) => {
+                let mut requested: Vec<Coin> = vec![];
+                for coin in funds.into_iter().filter(|coin| !coin.amount.is_zero()) {
+                    match requested.iter_mut().find(|req| req.denom == coin.denom) {
+                        Some(req) => req.amount += coin.amount,
+                        None => requested.push(coin),
+                    }
+                }
+                for coin in &requested {
+                    let available = available
+                        .iter()
+                        .find(|avail| avail.denom == coin.denom)
+                        .map(|avail| avail.amount)
+                        .unwrap_or_default();
+                    if coin.amount > available {
+                        return Err(ContractError::InsufficientFunds {
+                            requested: coin.clone(),
+                            available: Coin {
+                                denom: coin.denom.clone(),
+                                amount: available,
+                            },
+                        });
+                    }
+                }
+                requested
+            }
+            None => available
+                .into_iter()
+                .filter(|coin| !coin.amount.is_zero())
+                .collect(),
+        };
+        if amount.is_empty() {
+            return Err(ContractError::NothingToWithdraw {});
         }
-        let funds = deps.querier.query_all_balances(&env.contract.address)?;
+
         let bank_msg = BankMsg::Send {
-            to_address: owner.to_string(),
-            amount: funds,
+            to_address: recipient.to_string(),
+            amount,
         };
 
         let resp = Response::new()
             .add_message(bank_msg)
             .add_attribute("action", "withdraw")
-            .add_attribute("sender", info.sender.as_str());
+            .add_event(Withdrawn { owner, recipient }.into());
+
+        Ok(resp)
+    }
+
+    /// Replaces every qualifying threshold with `thresholds`.
+    pub fn save_thresholds(
+        storage: &mut dyn Storage,
+        thresholds: Vec<Coin>,
+    ) -> Result<(), ContractError> {
+        let denoms = MINIMAL_DONATIONS
+            .keys(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<_>>>()?;
+        for denom in denoms {
+            MINIMAL_DONATIONS.remove(storage, &denom);
+        }
+
+        for coin in thresholds {
+            if MINIMAL_DONATIONS.has(storage, &coin.denom) {
+                return Err(ContractError::DuplicateDenom { denom: coin.denom });
+            }
+            MINIMAL_DONATIONS.save(storage, &coin.denom, &coin.amount)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn update_thresholds(
+        deps: DepsMut,
+        info: MessageInfo,
+        thresholds: Vec<Coin>,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+        let count = thresholds.len();
+        save_thresholds(deps.storage, thresholds)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "update_thresholds")
+            .add_attribute("thresholds_count", count.to_string());
+
+        Ok(resp)
+    }
+
+    pub fn update_under_minimum_policy(
+        deps: DepsMut,
+        info: MessageInfo,
+        policy: UnderMinimumPolicy,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+        UNDER_MINIMUM_POLICY.save(deps.storage, &policy)?;
+
+        let resp = Response::new().add_attribute("action", "update_under_minimum_policy");
+
+        Ok(resp)
+    }
+
+    pub fn reset(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        value: u64,
+    ) -> Result<Response, ContractError> {
+        ensure_owner(deps.as_ref(), &info.sender)?;
+
+        let previous = COUNTER.load(deps.storage)?;
+        COUNTER.save(deps.storage, &value)?;
+
+        // contracts instantiated before resets existed start the count here
+        let id = RESET_COUNT.may_load(deps.storage)?.unwrap_or_default() + 1;
+        RESET_COUNT.save(deps.storage, &id)?;
+        let record = ResetRecord {
+            previous,
+            value,
+            height: env.block.height,
+            sender: info.sender.clone(),
+        };
+        RESETS.save(deps.storage, id, &record)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "reset")
+            .add_attribute("reset_id", id.to_string())
+            .add_event(
+                CounterReset {
+                    sender: info.sender,
+                    previous,
+                    counter: value,
+                }
+                .into(),
+            );
+
+        Ok(resp)
+    }
+
+    pub fn transfer_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+        new_owner: String,
+        expiry: Option<Expiration>,
+    ) -> Result<Response, ContractError> {
+        let owner = ensure_owner(deps.as_ref(), &info.sender)?;
+        let new_owner = deps.api.addr_validate(&new_owner)?;
+        if matches!(expiry, Some(expiry) if expiry.is_expired(&env.block)) {
+            return Err(ContractError::TransferExpired {});
+        }
+
+        let pending = PendingOwner {
+            owner: new_owner.clone(),
+            expiry,
+        };
+        PENDING_OWNER.save(deps.storage, &pending)?;
+
+        let resp = Response::new()
+            .add_attribute("action", "transfer_ownership")
+            .add_event(
+                OwnershipProposed {
+                    owner,
+                    pending_owner: new_owner,
+                }
+                .into(),
+            );
+
+        Ok(resp)
+    }
+
+    pub fn accept_ownership(
+        deps: DepsMut,
+        env: Env,
+        info: MessageInfo,
+    ) -> Result<Response, ContractError> {
+        let pending = PENDING_OWNER
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoPendingOwner {})?;
+        if info.sender != pending.owner {
+            return Err(ContractError::NotPendingOwner {
+                pending_owner: pending.owner.into_string(),
+            });
+        }
+        if matches!(pending.expiry, Some(expiry) if expiry.is_expired(&env.block)) {
+            return Err(ContractError::TransferExpired {});
+        }
+        // the offer only stands while the owner who made it keeps ownership
+        let previous_owner = OWNER
+            .may_load(deps.storage)?
+            .ok_or(ContractError::NoOwner {})?;
+
+        OWNER.save(deps.storage, &pending.owner)?;
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "accept_ownership")
+            .add_event(
+                OwnershipTransferred {
+                    previous_owner,
+                    owner: pending.owner,
+                }
+                .into(),
+            );
+
+        Ok(resp)
+    }
+
+    pub fn renounce_ownership(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+        let previous_owner = ensure_owner(deps.as_ref(), &info.sender)?;
+        OWNER.remove(deps.storage);
+        PENDING_OWNER.remove(deps.storage);
+
+        let resp = Response::new()
+            .add_attribute("action", "renounce_ownership")
+            .add_event(OwnershipRenounced { previous_owner }.into());
 
         Ok(resp)
     }
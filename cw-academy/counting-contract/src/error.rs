@@ -1,4 +1,4 @@
-use cosmwasm_std::StdError;
+use cosmwasm_std::{Coin, StdError};
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -8,4 +8,37 @@ pub enum ContractError {
 
     #[error("Unauthorized - only {owner} can it")]
     Unauthorized { owner: String },
+
+    #[error("Donation does not meet any minimal donation")]
+    BelowMinimum {},
+
+    #[error("Duplicate threshold for denom {denom}")]
+    DuplicateDenom { denom: String },
+
+    #[error("Cannot withdraw {requested}, only {available} available")]
+    InsufficientFunds { requested: Coin, available: Coin },
+
+    #[error("Nothing to withdraw")]
+    NothingToWithdraw {},
+
+    #[error("Ownership has been renounced")]
+    NoOwner {},
+
+    #[error("No ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("Unauthorized - only the pending owner {pending_owner} can accept ownership")]
+    NotPendingOwner { pending_owner: String },
+
+    #[error("Ownership transfer expired")]
+    TransferExpired {},
+
+    #[error("Cannot migrate from contract {actual}, expected {expected}")]
+    InvalidContractName { expected: String, actual: String },
+
+    #[error("Invalid contract version {version}: {reason}")]
+    InvalidVersion { version: String, reason: String },
+
+    #[error("Cannot migrate from version {stored} down to {current}")]
+    Downgrade { stored: String, current: String },
 }
@@ -0,0 +1,236 @@
+use cosmwasm_std::{Addr, Attribute, Event};
+
+/// A custom event emitted by the contract, convertible from and back into a
+/// raw `Event`.
+pub trait ContractEvent: Into<Event> + Sized {
+    /// Event type as set by the contract. The chain reports it prefixed with `wasm-`.
+    const TYPE: &'static str;
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self>;
+}
+
+/// Collects every event of type `T` out of `events`, as found in a transaction
+/// result or in cw-multi-test's `AppResponse::events`.
+pub fn parse<T: ContractEvent>(events: &[Event]) -> Vec<T> {
+    events
+        .iter()
+        .filter(|event| event.ty.strip_prefix("wasm-").unwrap_or(&event.ty) == T::TYPE)
+        .filter_map(|event| T::from_attributes(&event.attributes))
+        .collect()
+}
+
+fn attribute<'a>(attributes: &'a [Attribute], key: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|attr| attr.key == key)
+        .map(|attr| attr.value.as_str())
+}
+
+/// Emitted on every `Donate`, whether or not it bumped the counter.
+#[derive(PartialEq, Debug, Clone)]
+pub struct Donated {
+    pub sender: Addr,
+    pub counter: u64,
+    /// Ledger entry of the donation; `None` for a poke without funds.
+    pub donation_id: Option<u64>,
+}
+
+impl From<Donated> for Event {
+    fn from(event: Donated) -> Self {
+        let mut ev = Event::new(Donated::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("counter", event.counter.to_string());
+        if let Some(id) = event.donation_id {
+            ev = ev.add_attribute("donation_id", id.to_string());
+        }
+        ev
+    }
+}
+
+impl ContractEvent for Donated {
+    const TYPE: &'static str = "donated";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        let donation_id = match attribute(attributes, "donation_id") {
+            Some(id) => Some(id.parse().ok()?),
+            None => None,
+        };
+
+        Some(Self {
+            sender: Addr::unchecked(attribute(attributes, "sender")?),
+            counter: attribute(attributes, "counter")?.parse().ok()?,
+            donation_id,
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct Withdrawn {
+    pub owner: Addr,
+    pub recipient: Addr,
+}
+
+impl From<Withdrawn> for Event {
+    fn from(event: Withdrawn) -> Self {
+        Event::new(Withdrawn::TYPE)
+            .add_attribute("owner", event.owner)
+            .add_attribute("recipient", event.recipient)
+    }
+}
+
+impl ContractEvent for Withdrawn {
+    const TYPE: &'static str = "withdrawn";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            owner: Addr::unchecked(attribute(attributes, "owner")?),
+            recipient: Addr::unchecked(attribute(attributes, "recipient")?),
+        })
+    }
+}
+
+/// The owner restarted the count.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CounterReset {
+    pub sender: Addr,
+    pub previous: u64,
+    pub counter: u64,
+}
+
+impl From<CounterReset> for Event {
+    fn from(event: CounterReset) -> Self {
+        Event::new(CounterReset::TYPE)
+            .add_attribute("sender", event.sender)
+            .add_attribute("previous", event.previous.to_string())
+            .add_attribute("counter", event.counter.to_string())
+    }
+}
+
+impl ContractEvent for CounterReset {
+    const TYPE: &'static str = "counter_reset";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            sender: Addr::unchecked(attribute(attributes, "sender")?),
+            previous: attribute(attributes, "previous")?.parse().ok()?,
+            counter: attribute(attributes, "counter")?.parse().ok()?,
+        })
+    }
+}
+
+/// The owner offered ownership to `pending_owner`.
+#[derive(PartialEq, Debug, Clone)]
+pub struct OwnershipProposed {
+    pub owner: Addr,
+    pub pending_owner: Addr,
+}
+
+impl From<OwnershipProposed> for Event {
+    fn from(event: OwnershipProposed) -> Self {
+        Event::new(OwnershipProposed::TYPE)
+            .add_attribute("owner", event.owner)
+            .add_attribute("pending_owner", event.pending_owner)
+    }
+}
+
+impl ContractEvent for OwnershipProposed {
+    const TYPE: &'static str = "ownership_proposed";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            owner: Addr::unchecked(attribute(attributes, "owner")?),
+            pending_owner: Addr::unchecked(attribute(attributes, "pending_owner")?),
+        })
+    }
+}
+
+/// The pending owner accepted ownership.
+#[derive(PartialEq, Debug, Clone)]
+pub struct OwnershipTransferred {
+    pub previous_owner: Addr,
+    pub owner: Addr,
+}
+
+impl From<OwnershipTransferred> for Event {
+    fn from(event: OwnershipTransferred) -> Self {
+        Event::new(OwnershipTransferred::TYPE)
+            .add_attribute("previous_owner", event.previous_owner)
+            .add_attribute("owner", event.owner)
+    }
+}
+
+impl ContractEvent for OwnershipTransferred {
+    const TYPE: &'static str = "ownership_transferred";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            previous_owner: Addr::unchecked(attribute(attributes, "previous_owner")?),
+            owner: Addr::unchecked(attribute(attributes, "owner")?),
+        })
+    }
+}
+
+#[derive(PartialEq, Debug, Clone)]
+pub struct OwnershipRenounced {
+    pub previous_owner: Addr,
+}
+
+impl From<OwnershipRenounced> for Event {
+    fn from(event: OwnershipRenounced) -> Self {
+        Event::new(OwnershipRenounced::TYPE).add_attribute("previous_owner", event.previous_owner)
+    }
+}
+
+impl ContractEvent for OwnershipRenounced {
+    const TYPE: &'static str = "ownership_renounced";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            previous_owner: Addr::unchecked(attribute(attributes, "previous_owner")?),
+        })
+    }
+}
+
+/// The owner was replaced through a sudo override.
+#[derive(PartialEq, Debug, Clone)]
+pub struct OwnerSet {
+    pub owner: Addr,
+}
+
+impl From<OwnerSet> for Event {
+    fn from(event: OwnerSet) -> Self {
+        Event::new(OwnerSet::TYPE).add_attribute("owner", event.owner)
+    }
+}
+
+impl ContractEvent for OwnerSet {
+    const TYPE: &'static str = "owner_set";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            owner: Addr::unchecked(attribute(attributes, "owner")?),
+        })
+    }
+}
+
+/// The counter was overwritten through a sudo override.
+#[derive(PartialEq, Debug, Clone)]
+pub struct CounterSet {
+    pub counter: u64,
+}
+
+impl From<CounterSet> for Event {
+    fn from(event: CounterSet) -> Self {
+        Event::new(CounterSet::TYPE).add_attribute("counter", event.counter.to_string())
+    }
+}
+
+impl ContractEvent for CounterSet {
+    const TYPE: &'static str = "counter_set";
+
+    fn from_attributes(attributes: &[Attribute]) -> Option<Self> {
+        Some(Self {
+            counter: attribute(attributes, "counter")?.parse().ok()?,
+        })
+    }
+}
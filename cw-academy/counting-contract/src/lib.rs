@@ -2,9 +2,10 @@ use cosmwasm_std::{
     entry_point, to_binary, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
 };
 use error::ContractError;
-use msg::InstantiateMsg;
+use msg::{InstantiateMsg, MigrateMsg, SudoMsg};
 mod contract;
 mod error;
+pub mod events;
 pub mod msg;
 mod state;
 
@@ -14,18 +15,49 @@ pub fn instantiate(
     _env: Env,
     info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     contract::instantiate(deps, info, msg)
 }
 
 // DepsMut : want to change blockchain state
 // Deps : don't want to change blockchain state, just query the state
 #[entry_point]
-pub fn query(deps: Deps, _env: Env, msg: msg::QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: msg::QueryMsg) -> StdResult<Binary> {
     use msg::QueryMsg::*;
 
     match msg {
         Value {} => to_binary(&contract::query::value(deps)?),
+        ContractVersion {} => to_binary(&contract::query::contract_version(deps)?),
+        Ownership {} => to_binary(&contract::query::ownership(deps, env)?),
+        Thresholds {} => to_binary(&contract::query::thresholds(deps)?),
+        DonorStats { addr } => to_binary(&contract::query::donor_stats(deps, addr)?),
+        TopDonors {
+            denom,
+            start_after,
+            limit,
+        } => to_binary(&contract::query::top_donors(
+            deps,
+            denom,
+            start_after,
+            limit,
+        )?),
+        DonationHistory {
+            donor,
+            min_height,
+            max_height,
+            start_after,
+            limit,
+        } => to_binary(&contract::query::donation_history(
+            deps,
+            donor,
+            min_height,
+            max_height,
+            start_after,
+            limit,
+        )?),
+        ResetHistory { start_after, limit } => {
+            to_binary(&contract::query::reset_history(deps, start_after, limit)?)
+        }
     }
 }
 #[entry_point]
@@ -38,27 +70,79 @@ pub fn execute(
     use msg::ExecMsg::*;
 
     match msg {
-        Donate {} => contract::exec::donate(deps, info).map_err(ContractError::from),
-        Withdraw {} => contract::exec::withdraw(deps, env, info),
+        Donate {} => contract::exec::donate(deps, env, info),
+        Withdraw {
+            funds,
+            recipient,
+            reserve,
+        } => contract::exec::withdraw(deps, env, info, funds, recipient, reserve),
+        Reset { value } => contract::exec::reset(deps, env, info, value),
+        UpdateThresholds { thresholds } => {
+            contract::exec::update_thresholds(deps, info, thresholds)
+        }
+        UpdateUnderMinimumPolicy { policy } => {
+            contract::exec::update_under_minimum_policy(deps, info, policy)
+        }
+        TransferOwnership { new_owner, expiry } => {
+            contract::exec::transfer_ownership(deps, env, info, new_owner, expiry)
+        }
+        AcceptOwnership {} => contract::exec::accept_ownership(deps, env, info),
+        RenounceOwnership {} => contract::exec::renounce_ownership(deps, info),
+    }
+}
+
+#[entry_point]
+pub fn sudo(deps: DepsMut, _env: Env, msg: SudoMsg) -> StdResult<Response> {
+    use SudoMsg::*;
+
+    match msg {
+        SetOwner { owner } => contract::sudo::set_owner(deps, owner),
+        SetCounter { value } => contract::sudo::set_counter(deps, value),
     }
 }
 
+#[entry_point]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> Result<Response, ContractError> {
+    contract::migrate(deps)
+}
+
 #[cfg(test)]
 mod test {
-    use std::{mem, vec};
+    use std::vec;
 
-    use crate::msg::{ExecMsg, QueryMsg, ValueResp};
+    use crate::events::{
+        CounterReset, CounterSet, Donated, OwnerSet, OwnershipProposed, OwnershipRenounced,
+        OwnershipTransferred, Withdrawn,
+    };
+    use crate::msg::{
+        DonationHistoryResp, DonorStatsResp, DonorTallyResp, ExecMsg, OwnershipResp, QueryMsg,
+        ResetHistoryResp, ResetResp, ThresholdsResp, TopDonorsResp, UnderMinimumPolicy, ValueResp,
+    };
 
     use super::*;
-    use cosmwasm_std::{coins, Addr, Coin, Empty};
+    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
+    use cosmwasm_std::{coins, from_binary, Addr, Coin, Empty};
+    use cw2::ContractVersion;
     use cw_multi_test::{App, AppBuilder, Contract, ContractWrapper, Executor};
+    use cw_utils::Expiration;
 
     fn counting_contract() -> Box<dyn Contract<Empty>> {
-        let contract = ContractWrapper::new(execute, instantiate, query);
+        let contract = ContractWrapper::new(execute, instantiate, query)
+            .with_sudo(sudo)
+            .with_migrate(migrate);
         Box::new(contract)
     }
 
+    fn withdraw_all() -> ExecMsg {
+        ExecMsg::Withdraw {
+            funds: None,
+            recipient: None,
+            reserve: None,
+        }
+    }
+
     const ATOM: &str = "atom";
+    const STAR: &str = "star";
 
     #[test]
     fn query_value() {
@@ -69,7 +153,8 @@ mod test {
                 contract_id,
                 Addr::unchecked("sender"),
                 &InstantiateMsg {
-                    minimal_donation: Coin::new(10, ATOM),
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
                 },
                 &[],
                 "Counting Contract",
@@ -97,7 +182,8 @@ mod test {
                 contract_id,
                 Addr::unchecked("sender"),
                 &InstantiateMsg {
-                    minimal_donation: Coin::new(10, ATOM),
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
                 },
                 &[],
                 "Counting Contract",
@@ -105,13 +191,26 @@ mod test {
             )
             .unwrap();
 
-        let _resp = app
-            .execute_contract(sender, contract_addr.clone(), &ExecMsg::Donate {}, &[])
+        let resp = app
+            .execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &[],
+            )
             .unwrap();
 
         // for item in resp.events.iter() {
         //     dbg!(item);
         // }
+        assert_eq!(
+            events::parse::<Donated>(&resp.events),
+            vec![Donated {
+                sender,
+                counter: 0,
+                donation_id: None,
+            }]
+        );
 
         let resp: ValueResp = app
             .wrap()
@@ -137,7 +236,8 @@ mod test {
                 contract_id,
                 Addr::unchecked("sender"),
                 &InstantiateMsg {
-                    minimal_donation: Coin::new(10, ATOM),
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
                 },
                 &[],
                 "Counting Contract",
@@ -167,6 +267,425 @@ mod test {
         );
     }
 
+    #[test]
+    fn donation_history() {
+        let sender1 = Addr::unchecked("sender1");
+        let sender2 = Addr::unchecked("sender2");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &sender1, coins(20, ATOM))
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &sender2, coins(5, ATOM))
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let donations = [
+            (&sender1, coins(10, ATOM)),
+            (&sender2, coins(5, ATOM)),
+            (&sender1, vec![]),
+            (&sender1, coins(10, ATOM)),
+        ];
+        for (sender, funds) in donations {
+            app.execute_contract(
+                sender.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &funds,
+            )
+            .unwrap();
+            app.update_block(|block| block.height += 1);
+        }
+        let start = app.block_info().height - 4;
+
+        let history = |donor: Option<&Addr>,
+                       min_height: Option<u64>,
+                       max_height: Option<u64>,
+                       start_after: Option<u64>,
+                       limit: Option<u32>| {
+            let resp: DonationHistoryResp = app
+                .wrap()
+                .query_wasm_smart(
+                    contract_addr.clone(),
+                    &QueryMsg::DonationHistory {
+                        donor: donor.map(Addr::to_string),
+                        min_height,
+                        max_height,
+                        start_after,
+                        limit,
+                    },
+                )
+                .unwrap();
+            resp.donations
+                .into_iter()
+                .map(|donation| (donation.id, donation.height, donation.counted))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            history(None, None, None, None, None),
+            vec![
+                (1, start, true),
+                (2, start + 1, false),
+                (3, start + 3, true)
+            ]
+        );
+        assert_eq!(
+            history(None, None, None, Some(1), Some(1)),
+            vec![(2, start + 1, false)]
+        );
+        assert_eq!(
+            history(None, Some(start + 1), Some(start + 2), None, None),
+            vec![(2, start + 1, false)]
+        );
+        assert_eq!(
+            history(Some(&sender1), None, None, None, None),
+            vec![(1, start, true), (3, start + 3, true)]
+        );
+        assert_eq!(
+            history(Some(&sender1), Some(start + 1), None, None, None),
+            vec![(3, start + 3, true)]
+        );
+        assert_eq!(
+            history(Some(&sender1), None, Some(start), Some(1), None),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn donor_leaderboard() {
+        let donor1 = Addr::unchecked("donor1");
+        let donor2 = Addr::unchecked("donor2");
+        let donor3 = Addr::unchecked("donor3");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &donor1, coins(25, ATOM))
+                .unwrap();
+            router
+                .bank
+                .init_balance(storage, &donor2, coins(35, ATOM))
+                .unwrap();
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &donor3,
+                    vec![Coin::new(30, ATOM), Coin::new(7, STAR)],
+                )
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                Addr::unchecked("owner"),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let donations = [
+            (&donor1, coins(10, ATOM)),
+            (&donor2, coins(30, ATOM)),
+            (&donor3, coins(20, ATOM)),
+            (&donor1, coins(15, ATOM)),
+            // below the minimum, so it does not count
+            (&donor2, coins(5, ATOM)),
+            (&donor3, vec![Coin::new(10, ATOM), Coin::new(7, STAR)]),
+        ];
+        for (donor, funds) in donations {
+            app.execute_contract(
+                donor.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &funds,
+            )
+            .unwrap();
+        }
+
+        let tally = |donor: &Addr, denom: &str, count, amount: u128| DonorTallyResp {
+            donor: donor.clone(),
+            denom: denom.to_owned(),
+            count,
+            amount: amount.into(),
+        };
+
+        let resp: DonorStatsResp = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr.clone(),
+                &QueryMsg::DonorStats {
+                    addr: donor3.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.tallies,
+            vec![tally(&donor3, ATOM, 2, 30), tally(&donor3, STAR, 1, 7)]
+        );
+
+        let top_donors = |start_after: Option<&Addr>, limit: Option<u32>| {
+            let resp: TopDonorsResp = app
+                .wrap()
+                .query_wasm_smart(
+                    contract_addr.clone(),
+                    &QueryMsg::TopDonors {
+                        denom: ATOM.to_owned(),
+                        start_after: start_after.map(Addr::to_string),
+                        limit,
+                    },
+                )
+                .unwrap();
+            resp.donors
+        };
+
+        // equal amounts are ordered by address, descending
+        assert_eq!(
+            top_donors(None, None),
+            vec![
+                tally(&donor3, ATOM, 2, 30),
+                tally(&donor2, ATOM, 1, 30),
+                tally(&donor1, ATOM, 2, 25),
+            ]
+        );
+        assert_eq!(
+            top_donors(Some(&donor3), Some(1)),
+            vec![tally(&donor2, ATOM, 1, 30)]
+        );
+        assert_eq!(
+            top_donors(Some(&donor2), None),
+            vec![tally(&donor1, ATOM, 2, 25)]
+        );
+    }
+
+    #[test]
+    fn donation_thresholds() {
+        let owner = Addr::unchecked("owner");
+        let donor = Addr::unchecked("donor");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &donor,
+                    vec![Coin::new(10, ATOM), Coin::new(8, STAR)],
+                )
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: vec![Coin::new(10, ATOM), Coin::new(5, STAR)],
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let donate = |app: &mut App, funds: Vec<Coin>| -> u64 {
+            let resp = app
+                .execute_contract(
+                    donor.clone(),
+                    contract_addr.clone(),
+                    &ExecMsg::Donate {},
+                    &funds,
+                )
+                .unwrap();
+            events::parse::<Donated>(&resp.events)[0].counter
+        };
+        let update = |thresholds| ExecMsg::UpdateThresholds { thresholds };
+
+        // either threshold is enough on its own
+        assert_eq!(donate(&mut app, coins(5, STAR)), 1);
+        assert_eq!(donate(&mut app, coins(3, STAR)), 1);
+
+        let resp: ThresholdsResp = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Thresholds {})
+            .unwrap();
+        assert_eq!(
+            resp.thresholds,
+            vec![Coin::new(10, ATOM), Coin::new(5, STAR)]
+        );
+
+        let err = app
+            .execute_contract(
+                donor.clone(),
+                contract_addr.clone(),
+                &update(coins(1, STAR)),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                owner: owner.to_string()
+            },
+            err.downcast().unwrap()
+        );
+
+        let err = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &update(vec![Coin::new(1, STAR), Coin::new(2, STAR)]),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::DuplicateDenom {
+                denom: STAR.to_owned()
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(owner, contract_addr.clone(), &update(coins(20, STAR)), &[])
+            .unwrap();
+        let resp: ThresholdsResp = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Thresholds {})
+            .unwrap();
+        assert_eq!(resp.thresholds, coins(20, STAR));
+
+        // atom no longer qualifies
+        assert_eq!(donate(&mut app, coins(10, ATOM)), 1);
+    }
+
+    #[test]
+    fn under_minimum_policy() {
+        let owner = Addr::unchecked("owner");
+        let donor = Addr::unchecked("donor");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &donor, coins(15, ATOM))
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    under_minimum: UnderMinimumPolicy::Refund,
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let resp = app
+            .execute_contract(
+                donor.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &coins(5, ATOM),
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<Donated>(&resp.events),
+            vec![Donated {
+                sender: donor.clone(),
+                counter: 0,
+                donation_id: None,
+            }]
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&donor).unwrap(),
+            coins(15, ATOM)
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&contract_addr).unwrap(),
+            vec![]
+        );
+
+        app.execute_contract(
+            owner,
+            contract_addr.clone(),
+            &ExecMsg::UpdateUnderMinimumPolicy {
+                policy: UnderMinimumPolicy::Reject,
+            },
+            &[],
+        )
+        .unwrap();
+        let resp: ThresholdsResp = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Thresholds {})
+            .unwrap();
+        assert_eq!(resp.under_minimum, UnderMinimumPolicy::Reject);
+
+        let err = app
+            .execute_contract(
+                donor.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &coins(5, ATOM),
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::BelowMinimum {}, err.downcast().unwrap());
+
+        // qualifying donations are unaffected by the policy
+        app.execute_contract(
+            donor.clone(),
+            contract_addr.clone(),
+            &ExecMsg::Donate {},
+            &coins(10, ATOM),
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances(&donor).unwrap(),
+            coins(5, ATOM)
+        );
+
+        let resp: DonationHistoryResp = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::DonationHistory {
+                    donor: None,
+                    min_height: None,
+                    max_height: None,
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(resp.donations.len(), 1);
+        assert!(resp.donations[0].counted);
+    }
+
     #[test]
     fn withdraw() {
         let owner = Addr::unchecked("owner");
@@ -191,7 +710,8 @@ mod test {
                 contract_id,
                 owner.clone(),
                 &InstantiateMsg {
-                    minimal_donation: Coin::new(10, ATOM),
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
                 },
                 &[],
                 "Counting Contract",
@@ -217,14 +737,16 @@ mod test {
             )
             .unwrap();
 
-        let _ = app
-            .execute_contract(
-                owner.clone(),
-                contract_addr.clone(),
-                &ExecMsg::Withdraw {},
-                &[],
-            )
+        let resp = app
+            .execute_contract(owner.clone(), contract_addr.clone(), &withdraw_all(), &[])
             .unwrap();
+        assert_eq!(
+            events::parse::<Withdrawn>(&resp.events),
+            vec![Withdrawn {
+                owner: owner.clone(),
+                recipient: owner.clone(),
+            }]
+        );
 
         assert_eq!(
             app.wrap().query_all_balances(owner).unwrap(),
@@ -239,11 +761,20 @@ mod test {
     }
 
     #[test]
-    fn unauthorized_withdraw() {
+    fn partial_withdraw() {
         let owner = Addr::unchecked("owner");
-        let member = Addr::unchecked("member");
-
-        let mut app = App::default();
+        let donor = Addr::unchecked("donor");
+        let multisig = Addr::unchecked("multisig");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(
+                    storage,
+                    &donor,
+                    vec![Coin::new(30, ATOM), Coin::new(7, STAR)],
+                )
+                .unwrap();
+        });
 
         let contract_id = app.store_code(counting_contract());
         let contract_addr = app
@@ -251,7 +782,8 @@ mod test {
                 contract_id,
                 owner.clone(),
                 &InstantiateMsg {
-                    minimal_donation: Coin::new(10, ATOM),
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
                 },
                 &[],
                 "Counting Contract",
@@ -259,15 +791,580 @@ mod test {
             )
             .unwrap();
 
-        let err = app
-            .execute_contract(member, contract_addr, &ExecMsg::Withdraw {}, &[])
-            .unwrap_err();
+        app.execute_contract(
+            donor.clone(),
+            contract_addr.clone(),
+            &ExecMsg::Donate {},
+            &[Coin::new(30, ATOM), Coin::new(7, STAR)],
+        )
+        .unwrap();
 
-        assert_eq!(
-            ContractError::Unauthorized {
-                owner: owner.into()
-            },
+        let withdraw = |funds: Vec<Coin>, reserve: Vec<Coin>| ExecMsg::Withdraw {
+            funds: (!funds.is_empty()).then_some(funds),
+            recipient: Some(multisig.to_string()),
+            reserve: Some(reserve),
+        };
+
+        let resp = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &withdraw(coins(10, ATOM), coins(15, ATOM)),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<Withdrawn>(&resp.events),
+            vec![Withdrawn {
+                owner: owner.clone(),
+                recipient: multisig.clone(),
+            }]
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&multisig).unwrap(),
+            coins(10, ATOM)
+        );
+
+        let err = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &withdraw(coins(10, ATOM), coins(15, ATOM)),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InsufficientFunds {
+                requested: Coin::new(10, ATOM),
+                available: Coin::new(5, ATOM),
+            },
+            err.downcast().unwrap()
+        );
+        let err = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &withdraw(coins(8, STAR), vec![]),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::InsufficientFunds {
+                requested: Coin::new(8, STAR),
+                available: Coin::new(7, STAR),
+            },
+            err.downcast().unwrap()
+        );
+
+        // without explicit funds everything above the reserve goes
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &withdraw(vec![], coins(20, ATOM)),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances(&multisig).unwrap(),
+            vec![Coin::new(10, ATOM), Coin::new(7, STAR)]
+        );
+        assert_eq!(
+            app.wrap().query_all_balances(&contract_addr).unwrap(),
+            coins(20, ATOM)
+        );
+
+        let err = app
+            .execute_contract(
+                owner,
+                contract_addr,
+                &withdraw(vec![], coins(20, ATOM)),
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::NothingToWithdraw {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn unauthorized_withdraw() {
+        let owner = Addr::unchecked("owner");
+        let member = Addr::unchecked("member");
+
+        let mut app = App::default();
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let err = app
+            .execute_contract(member, contract_addr, &withdraw_all(), &[])
+            .unwrap_err();
+
+        assert_eq!(
+            ContractError::Unauthorized {
+                owner: owner.into()
+            },
+            err.downcast().unwrap()
+        );
+    }
+
+    #[test]
+    fn reset() {
+        let owner = Addr::unchecked("owner");
+        let member = Addr::unchecked("member");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &member, coins(20, ATOM))
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        for _ in 0..2 {
+            app.execute_contract(
+                member.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Donate {},
+                &coins(10, ATOM),
+            )
+            .unwrap();
+        }
+
+        let err = app
+            .execute_contract(
+                member.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Reset { value: 0 },
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                owner: owner.to_string()
+            },
             err.downcast().unwrap()
         );
+
+        let resp = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &ExecMsg::Reset { value: 0 },
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<CounterReset>(&resp.events),
+            vec![CounterReset {
+                sender: owner.clone(),
+                previous: 2,
+                counter: 0,
+            }]
+        );
+        app.update_block(|block| block.height += 1);
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecMsg::Reset { value: 5 },
+            &[],
+        )
+        .unwrap();
+
+        let resp: ValueResp = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResp { value: 5 });
+
+        let height = app.block_info().height;
+        let resp: ResetHistoryResp = app
+            .wrap()
+            .query_wasm_smart(
+                contract_addr,
+                &QueryMsg::ResetHistory {
+                    start_after: None,
+                    limit: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            resp.resets,
+            vec![
+                ResetResp {
+                    id: 1,
+                    previous: 2,
+                    value: 0,
+                    height: height - 1,
+                    sender: owner.clone(),
+                },
+                ResetResp {
+                    id: 2,
+                    previous: 0,
+                    value: 5,
+                    height,
+                    sender: owner,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ownership_transfer() {
+        let owner = Addr::unchecked("owner");
+        let new_owner = Addr::unchecked("new_owner");
+        let stranger = Addr::unchecked("stranger");
+        let mut app = App::default();
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        let ownership = |app: &App| -> OwnershipResp {
+            app.wrap()
+                .query_wasm_smart(contract_addr.clone(), &QueryMsg::Ownership {})
+                .unwrap()
+        };
+        let transfer = |to: &Addr, expiry| ExecMsg::TransferOwnership {
+            new_owner: to.to_string(),
+            expiry,
+        };
+
+        let expiry = Expiration::AtHeight(app.block_info().height + 10);
+        let resp = app
+            .execute_contract(
+                owner.clone(),
+                contract_addr.clone(),
+                &transfer(&new_owner, Some(expiry)),
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<OwnershipProposed>(&resp.events),
+            vec![OwnershipProposed {
+                owner: owner.clone(),
+                pending_owner: new_owner.clone(),
+            }]
+        );
+        assert_eq!(
+            ownership(&app),
+            OwnershipResp {
+                owner: Some(owner.clone()),
+                pending_owner: Some(new_owner.clone()),
+                pending_expiry: Some(expiry),
+            }
+        );
+
+        let err = app
+            .execute_contract(
+                stranger.clone(),
+                contract_addr.clone(),
+                &ExecMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(
+            ContractError::NotPendingOwner {
+                pending_owner: new_owner.to_string()
+            },
+            err.downcast().unwrap()
+        );
+
+        let resp = app
+            .execute_contract(
+                new_owner.clone(),
+                contract_addr.clone(),
+                &ExecMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<OwnershipTransferred>(&resp.events),
+            vec![OwnershipTransferred {
+                previous_owner: owner.clone(),
+                owner: new_owner.clone(),
+            }]
+        );
+
+        // withdraw follows the new owner
+        let err = app
+            .execute_contract(owner.clone(), contract_addr.clone(), &withdraw_all(), &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                owner: new_owner.to_string()
+            },
+            err.downcast().unwrap()
+        );
+        // an offer left unanswered lapses
+        let expiry = Expiration::AtHeight(app.block_info().height + 1);
+        app.execute_contract(
+            new_owner.clone(),
+            contract_addr.clone(),
+            &transfer(&stranger, Some(expiry)),
+            &[],
+        )
+        .unwrap();
+        app.update_block(|block| block.height += 1);
+        let err = app
+            .execute_contract(
+                stranger,
+                contract_addr.clone(),
+                &ExecMsg::AcceptOwnership {},
+                &[],
+            )
+            .unwrap_err();
+        assert_eq!(ContractError::TransferExpired {}, err.downcast().unwrap());
+        assert_eq!(ownership(&app).pending_owner, None);
+
+        let resp = app
+            .execute_contract(
+                new_owner.clone(),
+                contract_addr.clone(),
+                &ExecMsg::RenounceOwnership {},
+                &[],
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<OwnershipRenounced>(&resp.events),
+            vec![OwnershipRenounced {
+                previous_owner: new_owner.clone(),
+            }]
+        );
+        assert_eq!(
+            ownership(&app),
+            OwnershipResp {
+                owner: None,
+                pending_owner: None,
+                pending_expiry: None,
+            }
+        );
+
+        let err = app
+            .execute_contract(new_owner, contract_addr, &withdraw_all(), &[])
+            .unwrap_err();
+        assert_eq!(ContractError::NoOwner {}, err.downcast().unwrap());
+    }
+
+    #[test]
+    fn migrate_keeps_version() {
+        let owner = Addr::unchecked("owner");
+        let mut app = App::default();
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                Some(owner.to_string()),
+            )
+            .unwrap();
+
+        let new_contract_id = app.store_code(counting_contract());
+        app.migrate_contract(
+            owner,
+            contract_addr.clone(),
+            &MigrateMsg {},
+            new_contract_id,
+        )
+        .unwrap();
+
+        let resp: ContractVersion = app
+            .wrap()
+            .query_wasm_smart(contract_addr, &QueryMsg::ContractVersion {})
+            .unwrap();
+
+        assert_eq!(
+            resp,
+            ContractVersion {
+                contract: "crates.io:counting-contract".to_owned(),
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_rejects_downgrade() {
+        let mut deps = mock_dependencies();
+        cw2::set_contract_version(
+            deps.as_mut().storage,
+            "crates.io:counting-contract",
+            "99.0.0",
+        )
+        .unwrap();
+
+        let err = migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap_err();
+
+        assert_eq!(
+            err,
+            ContractError::Downgrade {
+                stored: "99.0.0".to_owned(),
+                current: env!("CARGO_PKG_VERSION").to_owned(),
+            }
+        );
+    }
+
+    #[test]
+    fn migrate_moves_legacy_threshold() {
+        // storage exactly as the first version left it, without any cw2 info
+        let mut deps = mock_dependencies();
+        state::COUNTER.save(deps.as_mut().storage, &3).unwrap();
+        state::LEGACY_MINIMAL_DONATION
+            .save(deps.as_mut().storage, &Coin::new(10, ATOM))
+            .unwrap();
+        state::OWNER
+            .save(deps.as_mut().storage, &Addr::unchecked("owner"))
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap(),
+            ContractVersion {
+                contract: "crates.io:counting-contract".to_owned(),
+                version: env!("CARGO_PKG_VERSION").to_owned(),
+            }
+        );
+
+        let resp: ThresholdsResp =
+            from_binary(&query(deps.as_ref(), mock_env(), QueryMsg::Thresholds {}).unwrap())
+                .unwrap();
+        assert_eq!(resp.thresholds, coins(10, ATOM));
+
+        // the ledger and its id counter did not exist before the upgrade
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("donor", &coins(10, ATOM)),
+            ExecMsg::Donate {},
+        )
+        .unwrap();
+        assert_eq!(
+            events::parse::<Donated>(&resp.events),
+            vec![Donated {
+                sender: Addr::unchecked("donor"),
+                counter: 4,
+                donation_id: Some(1),
+            }]
+        );
+    }
+
+    #[test]
+    fn sudo_overrides() {
+        let owner = Addr::unchecked("owner");
+        let new_owner = Addr::unchecked("new_owner");
+        let mut app = AppBuilder::new().build(|router, _api, storage| {
+            router
+                .bank
+                .init_balance(storage, &owner, coins(10, ATOM))
+                .unwrap();
+        });
+
+        let contract_id = app.store_code(counting_contract());
+        let contract_addr = app
+            .instantiate_contract(
+                contract_id,
+                owner.clone(),
+                &InstantiateMsg {
+                    minimal_donations: coins(10, ATOM),
+                    ..Default::default()
+                },
+                &[],
+                "Counting Contract",
+                None,
+            )
+            .unwrap();
+
+        app.execute_contract(
+            owner.clone(),
+            contract_addr.clone(),
+            &ExecMsg::Donate {},
+            &coins(10, ATOM),
+        )
+        .unwrap();
+
+        let resp = app
+            .wasm_sudo(contract_addr.clone(), &SudoMsg::SetCounter { value: 0 })
+            .unwrap();
+        assert_eq!(
+            events::parse::<CounterSet>(&resp.events),
+            vec![CounterSet { counter: 0 }]
+        );
+        let resp = app
+            .wasm_sudo(
+                contract_addr.clone(),
+                &SudoMsg::SetOwner {
+                    owner: new_owner.to_string(),
+                },
+            )
+            .unwrap();
+        assert_eq!(
+            events::parse::<OwnerSet>(&resp.events),
+            vec![OwnerSet {
+                owner: new_owner.clone()
+            }]
+        );
+
+        let resp: ValueResp = app
+            .wrap()
+            .query_wasm_smart(contract_addr.clone(), &QueryMsg::Value {})
+            .unwrap();
+        assert_eq!(resp, ValueResp { value: 0 });
+
+        let err = app
+            .execute_contract(owner, contract_addr.clone(), &withdraw_all(), &[])
+            .unwrap_err();
+        assert_eq!(
+            ContractError::Unauthorized {
+                owner: new_owner.to_string()
+            },
+            err.downcast().unwrap()
+        );
+
+        app.execute_contract(new_owner.clone(), contract_addr, &withdraw_all(), &[])
+            .unwrap();
+        assert_eq!(
+            app.wrap().query_all_balances(new_owner).unwrap(),
+            coins(10, ATOM)
+        );
     }
 }
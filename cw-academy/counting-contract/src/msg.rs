@@ -1,9 +1,37 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
-use cosmwasm_std::Coin;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw_utils::Expiration;
 
 #[cw_serde]
+#[derive(Default)]
 pub struct InstantiateMsg {
-    pub minimal_donation: Coin,
+    /// A donation counts once it meets any of these, at most one per denom.
+    pub minimal_donations: Vec<Coin>,
+    #[serde(default)]
+    pub under_minimum: UnderMinimumPolicy,
+}
+
+/// What happens to donated funds that meet none of the minimal donations.
+#[cw_serde]
+#[derive(Default)]
+pub enum UnderMinimumPolicy {
+    /// The contract keeps them without counting.
+    #[default]
+    Keep,
+    /// They are sent back to the donor in the same transaction.
+    Refund,
+    /// The donation fails.
+    Reject,
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
+
+/// Chain-governance overrides, executed without the owner's signature.
+#[cw_serde]
+pub enum SudoMsg {
+    SetOwner { owner: String },
+    SetCounter { value: u64 },
 }
 
 #[cw_serde]
@@ -11,14 +39,135 @@ pub struct InstantiateMsg {
 pub enum QueryMsg {
     #[returns(ValueResp)]
     Value {},
+    #[returns(cw2::ContractVersion)]
+    ContractVersion {},
+    /// Recorded donations, oldest first, optionally narrowed to one donor
+    /// and an inclusive block height range.
+    #[returns(DonationHistoryResp)]
+    DonationHistory {
+        donor: Option<String>,
+        min_height: Option<u64>,
+        max_height: Option<u64>,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    #[returns(OwnershipResp)]
+    Ownership {},
+    #[returns(ThresholdsResp)]
+    Thresholds {},
+    /// Qualifying donation totals of `addr`, one entry per denom.
+    #[returns(DonorStatsResp)]
+    DonorStats { addr: String },
+    /// Donors ranked by the amount of `denom` given, largest first.
+    /// `start_after` is the last donor of the previous page.
+    #[returns(TopDonorsResp)]
+    TopDonors {
+        denom: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Owner resets of the counter, oldest first.
+    #[returns(ResetHistoryResp)]
+    ResetHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
 pub enum ExecMsg {
     Donate {},
-    Withdraw {},
+    /// Sends `funds` (everything but `reserve` if omitted) to `recipient`,
+    /// the owner by default, while keeping `reserve` in the contract.
+    Withdraw {
+        funds: Option<Vec<Coin>>,
+        recipient: Option<String>,
+        reserve: Option<Vec<Coin>>,
+    },
+    /// Sets the counter to `value`; owner only.
+    Reset {
+        value: u64,
+    },
+    /// Replaces the qualifying thresholds; owner only.
+    UpdateThresholds {
+        thresholds: Vec<Coin>,
+    },
+    /// Changes how donations under every threshold are handled; owner only.
+    UpdateUnderMinimumPolicy {
+        policy: UnderMinimumPolicy,
+    },
+    /// Offers ownership to `new_owner`, who has to accept it before `expiry`.
+    /// Replaces any transfer already pending.
+    TransferOwnership {
+        new_owner: String,
+        expiry: Option<Expiration>,
+    },
+    AcceptOwnership {},
+    /// Leaves the contract without an owner for good.
+    RenounceOwnership {},
 }
 #[cw_serde]
 pub struct ValueResp {
     pub value: u64,
 }
+
+#[cw_serde]
+pub struct DonationResp {
+    pub id: u64,
+    pub donor: Addr,
+    pub coins: Vec<Coin>,
+    pub height: u64,
+    pub time: Timestamp,
+    pub counted: bool,
+}
+
+#[cw_serde]
+pub struct DonationHistoryResp {
+    pub donations: Vec<DonationResp>,
+}
+
+#[cw_serde]
+pub struct ThresholdsResp {
+    pub thresholds: Vec<Coin>,
+    pub under_minimum: UnderMinimumPolicy,
+}
+
+#[cw_serde]
+pub struct DonorTallyResp {
+    pub donor: Addr,
+    pub denom: String,
+    pub count: u64,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct DonorStatsResp {
+    pub tallies: Vec<DonorTallyResp>,
+}
+
+#[cw_serde]
+pub struct TopDonorsResp {
+    pub donors: Vec<DonorTallyResp>,
+}
+
+#[cw_serde]
+pub struct OwnershipResp {
+    /// `None` once ownership was renounced.
+    pub owner: Option<Addr>,
+    pub pending_owner: Option<Addr>,
+    pub pending_expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct ResetResp {
+    pub id: u64,
+    pub previous: u64,
+    pub value: u64,
+    pub height: u64,
+    pub sender: Addr,
+}
+
+#[cw_serde]
+pub struct ResetHistoryResp {
+    pub resets: Vec<ResetResp>,
+}
@@ -1,8 +1,112 @@
-use cosmwasm_std::{Addr, Coin};
-use cw_storage_plus::Item;
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Timestamp, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
+
+use crate::msg::UnderMinimumPolicy;
 
 // blockchain is just a key-value database
 // in this case, access key is 'counter'
 pub const COUNTER: Item<u64> = Item::new("counter");
-pub const MINIMAL_DONATION: Item<Coin> = Item::new("minimal_donation");
+// smallest donation that bumps the counter, keyed by denom
+pub const MINIMAL_DONATIONS: Map<&str, Uint128> = Map::new("minimal_donations");
+pub const UNDER_MINIMUM_POLICY: Item<UnderMinimumPolicy> = Item::new("under_minimum_policy");
+// single-denom threshold of older versions, only read by `migrate`
+pub const LEGACY_MINIMAL_DONATION: Item<Coin> = Item::new("minimal_donation");
+// absent once the owner renounced
 pub const OWNER: Item<Addr> = Item::new("owner");
+pub const PENDING_OWNER: Item<PendingOwner> = Item::new("pending_owner");
+
+/// An ownership transfer waiting for the new owner to accept it.
+#[cw_serde]
+pub struct PendingOwner {
+    pub owner: Addr,
+    pub expiry: Option<Expiration>,
+}
+
+#[cw_serde]
+pub struct DonationRecord {
+    pub donor: Addr,
+    pub coins: Vec<Coin>,
+    pub height: u64,
+    pub time: Timestamp,
+    /// Whether the donation met the minimum and bumped the counter.
+    pub counted: bool,
+}
+
+pub struct DonationIndexes<'a> {
+    pub donor: MultiIndex<'a, Addr, DonationRecord, u64>,
+    pub height: MultiIndex<'a, u64, DonationRecord, u64>,
+}
+
+impl<'a> IndexList<DonationRecord> for DonationIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DonationRecord>> + '_> {
+        let v: Vec<&dyn Index<DonationRecord>> = vec![&self.donor, &self.height];
+        Box::new(v.into_iter())
+    }
+}
+
+// every donation, keyed by a sequential id
+pub fn donations<'a>() -> IndexedMap<'a, u64, DonationRecord, DonationIndexes<'a>> {
+    let indexes = DonationIndexes {
+        donor: MultiIndex::new(
+            |_, record: &DonationRecord| record.donor.clone(),
+            "donations",
+            "donations__donor",
+        ),
+        height: MultiIndex::new(
+            |_, record: &DonationRecord| record.height,
+            "donations",
+            "donations__height",
+        ),
+    };
+    IndexedMap::new("donations", indexes)
+}
+
+pub const DONATION_COUNT: Item<u64> = Item::new("donation_count");
+
+/// What one donor gave in one denom across their qualifying donations.
+#[cw_serde]
+pub struct DonorTally {
+    pub donor: Addr,
+    pub denom: String,
+    /// Qualifying donations that included this denom.
+    pub count: u64,
+    pub amount: Uint128,
+}
+
+pub struct DonorTallyIndexes<'a> {
+    pub amount: MultiIndex<'a, (String, u128), DonorTally, (Addr, String)>,
+}
+
+impl<'a> IndexList<DonorTally> for DonorTallyIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<DonorTally>> + '_> {
+        let v: Vec<&dyn Index<DonorTally>> = vec![&self.amount];
+        Box::new(v.into_iter())
+    }
+}
+
+// per-donor totals keyed by (donor, denom), indexed by amount within a denom
+pub fn donor_tallies<'a>() -> IndexedMap<'a, (&'a Addr, &'a str), DonorTally, DonorTallyIndexes<'a>>
+{
+    let indexes = DonorTallyIndexes {
+        amount: MultiIndex::new(
+            |_, tally: &DonorTally| (tally.denom.clone(), tally.amount.u128()),
+            "donor_tallies",
+            "donor_tallies__amount",
+        ),
+    };
+    IndexedMap::new("donor_tallies", indexes)
+}
+
+#[cw_serde]
+pub struct ResetRecord {
+    pub previous: u64,
+    pub value: u64,
+    pub height: u64,
+    pub sender: Addr,
+}
+
+// every owner reset of the counter, keyed by a sequential id
+pub const RESETS: Map<u64, ResetRecord> = Map::new("resets");
+pub const RESET_COUNT: Item<u64> = Item::new("reset_count");